@@ -3,14 +3,17 @@
 //! date: 2023/02/12 22:33:08 Sunday
 //! brief: Eclectic
 
+use std::collections::HashMap;
 use std::ops::Deref;
 
+use arrow2::array::PrimitiveArray;
 use arrow2::chunk::Chunk;
 use arrow2::compute::concatenate::concatenate;
+use arrow2::compute::take::take;
 
 use super::FxSeq;
 use crate::ab::{private, Confined};
-use crate::cont::ArcArr;
+use crate::cont::{ArcArr, FxBatch, PartitionKey};
 use crate::error::{FxError, FxResult};
 
 // ================================================================================================
@@ -67,6 +70,45 @@ pub trait Eclectic: private::InnerEclectic + Sized {
     fn check_nulls(&self) -> Vec<bool> {
         self.ref_sequences().iter().map(|s| s.has_null()).collect()
     }
+
+    /// Splits rows by the values of `columns` (positions), each partition keeps the original
+    /// row order. Resulting batches carry default column names.
+    fn partition_by(&self, columns: &[usize]) -> FxResult<HashMap<PartitionKey, FxBatch>>
+    where
+        Self: private::InnerEclectic<Seq = ArcArr>,
+    {
+        if !self.is_lens_same() {
+            return Err(FxError::InvalidOperation(
+                "lengths are not the same".to_string(),
+            ));
+        }
+
+        let seqs = self.ref_sequences();
+        let len = self.max_len().unwrap_or(0);
+        let values = columns
+            .iter()
+            .map(|&c| seqs.get(c).ok_or(FxError::OutBounds)?.to_values())
+            .collect::<FxResult<Vec<_>>>()?;
+
+        let mut groups: HashMap<PartitionKey, Vec<u32>> = HashMap::new();
+        for row in 0..len {
+            let key = PartitionKey::from(values.iter().map(|v| v[row].clone()).collect::<Vec<_>>());
+            groups.entry(key).or_default().push(row as u32);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, rows)| {
+                let indices = PrimitiveArray::<u32>::from_vec(rows);
+                let arrays = seqs
+                    .iter()
+                    .map(|s| take(s.as_ref(), &indices).map(ArcArr::from))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok((key, FxBatch::try_new(arrays)?))
+            })
+            .collect()
+    }
 }
 
 impl<T> Eclectic for T where T: private::InnerEclectic {}
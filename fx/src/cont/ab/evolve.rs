@@ -0,0 +1,359 @@
+//! file: evolve.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 15:20:47 Sunday
+//! brief: Schema evolution
+
+use arrow2::array::Array;
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::datatypes::{DataType, Field, Schema};
+
+use super::{Eclectic, FxSeq, Purport};
+use crate::cont::DequeArcArr;
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// SchemaChange
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// A new nullable column has been appended, and the existing rows are back-filled by nulls.
+    Added(Field),
+    /// A numeric column has been widened.
+    Widened {
+        name: String,
+        from: DataType,
+        to: DataType,
+    },
+}
+
+// ================================================================================================
+// Evolve
+//
+// An opt-in alternative of `Receptacle::push`. Instead of rejecting data whose types differ by
+// `FxError::SchemaMismatch`, the receptacle's schema evolves:
+// 1. incoming columns are matched by name, hence the order can be different;
+// 2. numeric columns are widened (e.g. `Int32` & `Float64` -> `Float64`);
+// 3. new columns are appended as nullable, existing rows are back-filled by nulls.
+// Fixed width containers (`FxBundles` & `FxTable`) cannot add new columns.
+// ================================================================================================
+
+pub trait Evolve<E: Eclectic>: Purport {
+    /// Appends `data` whose schema is described by `schema`, and returns the schema changes.
+    /// # Errors
+    /// This function will return an error if `data` is not described by `schema`, or columns
+    /// cannot be matched or widened.
+    fn push_evolving(&mut self, data: E, schema: &Schema) -> FxResult<Vec<SchemaChange>>;
+}
+
+// ================================================================================================
+// Widen
+// ================================================================================================
+
+// (is signed, bits)
+fn int_info(datatype: &DataType) -> Option<(bool, u8)> {
+    match datatype {
+        DataType::Int8 => Some((true, 8)),
+        DataType::Int16 => Some((true, 16)),
+        DataType::Int32 => Some((true, 32)),
+        DataType::Int64 => Some((true, 64)),
+        DataType::UInt8 => Some((false, 8)),
+        DataType::UInt16 => Some((false, 16)),
+        DataType::UInt32 => Some((false, 32)),
+        DataType::UInt64 => Some((false, 64)),
+        _ => None,
+    }
+}
+
+fn int_type(signed: bool, bits: u8) -> Option<DataType> {
+    match (signed, bits) {
+        (true, 8) => Some(DataType::Int8),
+        (true, 16) => Some(DataType::Int16),
+        (true, 32) => Some(DataType::Int32),
+        (true, 64) => Some(DataType::Int64),
+        (false, 8) => Some(DataType::UInt8),
+        (false, 16) => Some(DataType::UInt16),
+        (false, 32) => Some(DataType::UInt32),
+        (false, 64) => Some(DataType::UInt64),
+        _ => None,
+    }
+}
+
+/// Returns the narrowest numeric type which both `a` and `b` can be losslessly casted into.
+/// `None` if either of them is not numeric, or there is no such type (e.g. `UInt64` & `Int8`,
+/// or `Int64` & `Float64`, since `Float64` cannot hold every 64-bit integer).
+pub fn widen_numeric(a: &DataType, b: &DataType) -> Option<DataType> {
+    if a == b {
+        return Some(a.clone());
+    }
+
+    match (a, b) {
+        (DataType::Float32, DataType::Float64) | (DataType::Float64, DataType::Float32) => {
+            Some(DataType::Float64)
+        }
+        (DataType::Float32 | DataType::Float64, i) | (i, DataType::Float32 | DataType::Float64) => {
+            let (_, bits) = int_info(i)?;
+            if bits > 32 {
+                return None;
+            }
+            let float = if a == &DataType::Float64 || b == &DataType::Float64 || bits > 16 {
+                DataType::Float64
+            } else {
+                DataType::Float32
+            };
+            Some(float)
+        }
+        _ => {
+            let (sa, ba) = int_info(a)?;
+            let (sb, bb) = int_info(b)?;
+            if sa == sb {
+                return int_type(sa, ba.max(bb));
+            }
+            // signed & unsigned: the signed one should be wider than the unsigned one
+            let (us_bits, s_bits) = if sa { (bb, ba) } else { (ba, bb) };
+            int_type(true, s_bits.max(us_bits * 2))
+        }
+    }
+}
+
+// arrow2 turns values which cannot be casted into nulls, which are rejected here
+fn check_casted(from: &dyn Array, to: &dyn Array) -> FxResult<()> {
+    if from.null_count() != to.null_count() {
+        return Err(FxError::InvalidArgument(format!(
+            "values of {:?} cannot be casted into {:?}",
+            from.data_type(),
+            to.data_type()
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn cast_seq<S: FxSeq>(seq: S, datatype: &DataType) -> FxResult<S> {
+    if seq.data_type() == datatype {
+        return Ok(seq);
+    }
+
+    let arr = seq.to_arc_array()?;
+    let casted = cast(arr.as_ref(), datatype, CastOptions::default())?;
+    check_casted(arr.as_ref(), casted.as_ref())?;
+
+    Ok(S::from_box_arr(casted))
+}
+
+pub(crate) fn cast_deque(dq: &DequeArcArr, datatype: &DataType) -> FxResult<DequeArcArr> {
+    let casted = dq.try_cast(datatype)?;
+    for (from, to) in dq.iter().zip(casted.iter()) {
+        check_casted(from.as_ref(), to.as_ref())?;
+    }
+
+    Ok(casted)
+}
+
+/// Checks the incoming data is described by `schema`, i.e. the same number of sequences and
+/// the same types, since the plan is made by `schema` only.
+pub(crate) fn check_incoming<E: Eclectic>(data: &E, schema: &Schema) -> FxResult<()> {
+    let seqs = data.sequences();
+    if seqs.len() != schema.fields.len()
+        || seqs
+            .iter()
+            .zip(schema.fields.iter())
+            .any(|(s, f)| s.data_type() != f.data_type())
+    {
+        return Err(FxError::SchemaMismatch);
+    }
+
+    Ok(())
+}
+
+// ================================================================================================
+// EvolvePlan
+// ================================================================================================
+
+#[derive(Debug)]
+pub(crate) struct EvolvePlan {
+    /// the resulting schema
+    pub(crate) schema: Schema,
+    /// existing column's index & its widened type
+    pub(crate) widened: Vec<(usize, DataType)>,
+    /// fields appended after existing columns
+    pub(crate) added: Vec<Field>,
+    /// for each column of the resulting schema, its position in the incoming data
+    pub(crate) order: Vec<usize>,
+    pub(crate) changes: Vec<SchemaChange>,
+}
+
+impl EvolvePlan {
+    pub(crate) fn try_new(current: &Schema, incoming: &Schema, extensible: bool) -> FxResult<Self> {
+        let mut fields = current.fields.clone();
+        let mut widened = Vec::new();
+        let mut added = Vec::new();
+        let mut order = Vec::new();
+        let mut changes = Vec::new();
+
+        if incoming.fields.len() < fields.len() {
+            return Err(FxError::LengthMismatch(incoming.fields.len(), fields.len()));
+        }
+
+        for (idx, field) in fields.iter_mut().enumerate() {
+            let pos = incoming
+                .fields
+                .iter()
+                .position(|f| f.name == field.name)
+                .ok_or(FxError::SchemaMismatch)?;
+            let inc = &incoming.fields[pos];
+
+            let datatype =
+                widen_numeric(field.data_type(), inc.data_type()).ok_or(FxError::SchemaMismatch)?;
+            if &datatype != field.data_type() {
+                changes.push(SchemaChange::Widened {
+                    name: field.name.clone(),
+                    from: field.data_type().clone(),
+                    to: datatype.clone(),
+                });
+                widened.push((idx, datatype.clone()));
+                field.data_type = datatype;
+            }
+            field.is_nullable |= inc.is_nullable;
+
+            order.push(pos);
+        }
+
+        for (pos, inc) in incoming.fields.iter().enumerate() {
+            if order.contains(&pos) {
+                continue;
+            }
+            if !extensible {
+                return Err(FxError::SchemaMismatch);
+            }
+            if fields.iter().any(|f| f.name == inc.name) {
+                return Err(FxError::InvalidArgument(format!(
+                    "duplicated column `{}`",
+                    inc.name
+                )));
+            }
+
+            let field = Field::new(inc.name.clone(), inc.data_type().clone(), true);
+            changes.push(SchemaChange::Added(field.clone()));
+            added.push(field.clone());
+            fields.push(field);
+            order.push(pos);
+        }
+
+        let schema = Schema::from(fields).with_metadata(current.metadata.clone());
+
+        Ok(Self {
+            schema,
+            widened,
+            added,
+            order,
+            changes,
+        })
+    }
+
+    /// True if the existing data is required to be modified.
+    pub(crate) fn alters_existing(&self) -> bool {
+        !self.widened.is_empty() || !self.added.is_empty()
+    }
+
+    /// Widens and back-fills an existing [`Eclectic`].
+    pub(crate) fn evolve_existing<E: Eclectic>(&self, data: E) -> FxResult<E> {
+        let len = data.max_len().unwrap_or(0);
+        let mut seqs = data.take_sequences();
+
+        for (idx, datatype) in self.widened.iter() {
+            let s = seqs.remove(*idx);
+            seqs.insert(*idx, cast_seq(s, datatype)?);
+        }
+        for f in self.added.iter() {
+            seqs.push(E::Seq::new_nulls(f.data_type().clone(), len));
+        }
+
+        E::from_vec_seq(seqs)
+    }
+
+    /// Reorders and casts sequences of an incoming [`Eclectic`] by the resulting schema.
+    pub(crate) fn evolve_incoming_seqs<S: FxSeq>(&self, seqs: Vec<S>) -> FxResult<Vec<S>> {
+        let mut seqs = seqs.into_iter().map(Some).collect::<Vec<_>>();
+
+        self.order
+            .iter()
+            .zip(self.schema.fields.iter())
+            .map(|(pos, f)| {
+                let s = seqs
+                    .get_mut(*pos)
+                    .and_then(Option::take)
+                    .ok_or(FxError::OutBounds)?;
+                cast_seq(s, f.data_type())
+            })
+            .collect()
+    }
+
+    pub(crate) fn evolve_incoming<E: Eclectic>(&self, data: E) -> FxResult<E> {
+        E::from_vec_seq(self.evolve_incoming_seqs(data.take_sequences())?)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_evolve {
+    use super::*;
+    use crate::ab::FromSlice;
+    use crate::cont::ArcArr;
+
+    #[test]
+    fn cast_seq_success() {
+        let res = cast_seq(ArcArr::from_slice([1i32, 2]), &DataType::Int64).unwrap();
+        assert_eq!(res.data_type(), &DataType::Int64);
+
+        // values failed to cast are not turned into nulls
+        let res = cast_seq(ArcArr::from_slice(["1", "x"]), &DataType::Int64);
+        assert!(matches!(res, Err(FxError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn widen_numeric_success() {
+        use DataType::*;
+
+        assert_eq!(widen_numeric(&Int8, &Int32), Some(Int32));
+        assert_eq!(widen_numeric(&UInt8, &Int8), Some(Int16));
+        assert_eq!(widen_numeric(&UInt32, &Int64), Some(Int64));
+        assert_eq!(widen_numeric(&UInt64, &Int8), None);
+        assert_eq!(widen_numeric(&Int16, &Float32), Some(Float32));
+        assert_eq!(widen_numeric(&Int32, &Float32), Some(Float64));
+        assert_eq!(widen_numeric(&Float32, &Float64), Some(Float64));
+        assert_eq!(widen_numeric(&Int64, &Float64), None);
+        assert_eq!(widen_numeric(&UInt64, &Float32), None);
+        assert_eq!(widen_numeric(&Utf8, &Utf8), Some(Utf8));
+        assert_eq!(widen_numeric(&Utf8, &Int8), None);
+    }
+
+    #[test]
+    fn evolve_plan_success() {
+        let current = Schema::from(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let incoming = Schema::from(vec![
+            Field::new("b", DataType::Utf8, false),
+            Field::new("c", DataType::Boolean, false),
+            Field::new("a", DataType::Float64, false),
+        ]);
+
+        let plan = EvolvePlan::try_new(&current, &incoming, true).unwrap();
+        println!("{:?}", plan.changes);
+
+        assert_eq!(plan.order, vec![2, 0, 1]);
+        assert_eq!(plan.widened, vec![(0, DataType::Float64)]);
+        assert_eq!(plan.added.len(), 1);
+        assert!(plan.schema.fields[2].is_nullable);
+        // reordering is not a change of the resulting schema
+        assert_eq!(plan.changes.len(), 2);
+
+        let plan = EvolvePlan::try_new(&current, &incoming, false);
+        assert!(plan.is_err());
+    }
+}
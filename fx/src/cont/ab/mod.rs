@@ -8,6 +8,7 @@ pub mod confined;
 pub mod congruent;
 pub mod dqs;
 pub mod eclectic;
+pub mod evolve;
 pub(crate) mod private;
 pub mod purport;
 pub mod receptacle;
@@ -18,6 +19,7 @@ pub use confined::*;
 pub use congruent::*;
 pub use dqs::*;
 pub use eclectic::*;
+pub use evolve::*;
 pub use purport::*;
 pub use receptacle::*;
 pub use seq::*;
@@ -32,6 +32,9 @@ pub trait InnerEclectic: Confined {
     fn set_sequences_unchecked(&mut self, arrays: Vec<Self::Seq>) -> FxResult<()>;
 
     fn take_sequences(self) -> Vec<Self::Seq>;
+
+    // only those carrying a schema (e.g. `FxBatch` & `FxBundle`) need to override this
+    fn set_schema_unchecked(&mut self, _schema: Schema) {}
 }
 
 #[doc(hidden)]
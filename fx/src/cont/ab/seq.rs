@@ -120,6 +120,17 @@ pub trait FxSeq: Debug {
 
     fn extend(&mut self, s: &Self) -> FxResult<&mut Self>;
 
+    fn to_values(&self) -> FxResult<Vec<FxValue>>
+    where
+        Self: Sized,
+    {
+        if Self::is_arr() {
+            Ok(FxArrIntoIterator::new(self)?.collect())
+        } else {
+            Ok(FxVecIntoIterator::new(self)?.collect())
+        }
+    }
+
     fn concat(&mut self, ss: &[&Self]) -> FxResult<&mut Self> {
         for s in ss {
             Self::extend(self, s)?;
@@ -309,7 +320,14 @@ impl<'a, T: FxSeq> Iterator for FxVecIntoIterator<'a, T> {
             DataType::UInt64 => next_vec_val!(self, PVu64),
             DataType::Float32 => next_vec_val!(self, PVf32),
             DataType::Float64 => next_vec_val!(self, PVf64),
-            DataType::Utf8 => next_vec_val!(self, UV),
+            // `values` of a mutable utf8 array are its bytes
+            DataType::Utf8 => {
+                if let VecEnum::UV(v) = self.data {
+                    Some(FxValue::from(v.value(self.index)))
+                } else {
+                    None
+                }
+            }
             _ => unimplemented!(),
         };
         if val.is_some() {
@@ -418,4 +436,14 @@ mod test_seq {
         iter_bv.for_each(|e| println!("> {:?}", e));
         println!("\n");
     }
+
+    #[test]
+    fn utf8_vec_into_iter_success() {
+        let av = arc_vec!([Some("ab"), None, Some("c")]);
+        let bv = box_vec!([Some("ab"), None, Some("c")]);
+
+        let expected = vec![FxValue::from("ab"), FxValue::Null, FxValue::from("c")];
+        assert_eq!(av.into_iter().collect::<Vec<_>>(), expected);
+        assert_eq!(bv.into_iter().collect::<Vec<_>>(), expected);
+    }
 }
@@ -3,13 +3,15 @@
 //! date: 2023/01/20 12:36:42 Friday
 //! brief: Batch
 
+use std::collections::HashMap;
+
 use arrow2::chunk::Chunk;
 use arrow2::datatypes::Schema;
 use inherent::inherent;
 
-use crate::ab::{private, FxSeq, Purport, StaticPurport};
-use crate::cont::ArcArr;
-use crate::error::FxResult;
+use crate::ab::{private, Eclectic, FxSeq, Purport, StaticPurport};
+use crate::cont::{ArcArr, PartitionKey};
+use crate::error::{FxError, FxResult};
 
 use super::ChunkArr;
 
@@ -61,6 +63,10 @@ impl private::InnerEclectic for FxBatch {
     fn take_sequences(self) -> Vec<Self::Seq> {
         self.data.into_arrays()
     }
+
+    fn set_schema_unchecked(&mut self, schema: Schema) {
+        self.schema = schema;
+    }
 }
 
 impl private::InnerEclecticMutChunk for FxBatch {
@@ -118,6 +124,31 @@ impl FxBatch {
     pub fn data(&self) -> &ChunkArr {
         &self.data
     }
+
+    /// Same as [`Eclectic::partition_by`], but columns are named, and the schema is preserved.
+    pub fn partition_by_names<I, T>(&self, names: I) -> FxResult<HashMap<PartitionKey, FxBatch>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let columns = names
+            .into_iter()
+            .map(|n| {
+                let n = n.as_ref();
+                self.schema
+                    .fields
+                    .iter()
+                    .position(|f| f.name == n)
+                    .ok_or_else(|| FxError::InvalidArgument(format!("column `{n}` not found")))
+            })
+            .collect::<FxResult<Vec<_>>>()?;
+
+        let mut res = self.partition_by(&columns)?;
+        res.values_mut()
+            .for_each(|b| b.schema = self.schema.clone());
+
+        Ok(res)
+    }
 }
 
 // ================================================================================================
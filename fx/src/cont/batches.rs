@@ -6,6 +6,7 @@
 use arrow2::datatypes::{Field, Schema};
 use inherent::inherent;
 
+use crate::ab::evolve::{check_incoming, EvolvePlan};
 use crate::ab::*;
 use crate::error::FxResult;
 
@@ -78,6 +79,41 @@ impl<E: Eclectic + Confined> private::InnerReceptacle<true, usize, E> for FxBatc
     }
 }
 
+// ================================================================================================
+// Evolve
+// ================================================================================================
+
+impl<E: Eclectic + Confined + Clone> Evolve<E> for FxBatches<E> {
+    fn push_evolving(&mut self, data: E, schema: &Schema) -> FxResult<Vec<SchemaChange>> {
+        check_incoming(&data, schema)?;
+
+        // an empty receptacle simply adopts the incoming schema
+        if self.data.is_empty() && self.schema.fields.is_empty() {
+            self.schema = schema.clone();
+            self.data.push(data);
+            return Ok(vec![]);
+        }
+
+        let plan = EvolvePlan::try_new(&self.schema, schema, true)?;
+
+        let data = plan.evolve_incoming(data)?;
+        // evolve copies (arrays are shared), hence nothing changes if any cast fails
+        if plan.alters_existing() {
+            let evolved = self
+                .data
+                .iter()
+                .cloned()
+                .map(|e| plan.evolve_existing(e))
+                .collect::<FxResult<Vec<_>>>()?;
+            self.data = evolved;
+        }
+        self.data.push(data);
+        self.schema = plan.schema;
+
+        Ok(plan.changes)
+    }
+}
+
 impl<E: Eclectic> FxBatches<E> {
     pub fn new(data: Vec<E>) -> Self {
         if data.is_empty() {
@@ -117,6 +153,8 @@ impl<E: Eclectic> FxBatches<E> {
 mod test_batches {
     use super::*;
 
+    use arrow2::datatypes::DataType;
+
     use crate::cont::{ArcArr, ChunkArr, FxBatch};
 
     #[test]
@@ -164,4 +202,51 @@ mod test_batches {
 
         println!("{b:?}");
     }
+
+    #[test]
+    fn push_evolving_success() {
+        let ca = ChunkArr::new(vec![
+            ArcArr::from_slice([1i32, 2]),
+            ArcArr::from_slice(["a", "b"]),
+        ]);
+        let mut b = FxBatches::new_with_names(vec![ca], ["c1", "c2"]);
+
+        let ca = ChunkArr::new(vec![
+            ArcArr::from_slice([Some(true), None]),
+            ArcArr::from_slice(["c", "d"]),
+            ArcArr::from_slice([3i64, 4]),
+        ]);
+        let schema = Schema::from(vec![
+            Field::new("c3", DataType::Boolean, true),
+            Field::new("c2", DataType::Utf8, false),
+            Field::new("c1", DataType::Int64, false),
+        ]);
+
+        let changes = b.push_evolving(ca, &schema).unwrap();
+        println!("{changes:?}");
+        assert_eq!(changes.len(), 2);
+
+        assert_eq!(b.names(), vec!["c1", "c2", "c3"]);
+        for ca in b.data.iter() {
+            assert_eq!(ca.data_types(), b.data_types());
+        }
+        // back-filled
+        assert_eq!(b.data[0].arrays()[2].null_count(), 2);
+
+        // the data should be described by the schema
+        let schema = Schema::from(vec![Field::new("c1", DataType::Int64, false)]);
+        let mut b = FxBatches::new_with_names(
+            vec![ChunkArr::new(vec![ArcArr::from_slice([1i64])])],
+            ["c1"],
+        );
+        let ca = ChunkArr::new(vec![ArcArr::from_slice(["x", "y"])]);
+        let res = b.push_evolving(ca.clone(), &schema);
+        assert!(matches!(res, Err(crate::error::FxError::SchemaMismatch)));
+        assert_eq!(b.data.len(), 1);
+
+        let mut b = FxBatches::<ChunkArr>::new(vec![]);
+        let res = b.push_evolving(ca, &schema);
+        assert!(matches!(res, Err(crate::error::FxError::SchemaMismatch)));
+        assert!(b.data.is_empty());
+    }
 }
@@ -79,6 +79,10 @@ where
 
         res
     }
+
+    fn set_schema_unchecked(&mut self, schema: Schema) {
+        self.schema = schema;
+    }
 }
 
 impl<const W: usize, S> private::InnerEclecticMutSeq for FxBundle<W, S>
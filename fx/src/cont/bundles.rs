@@ -6,7 +6,8 @@
 use arrow2::datatypes::{Field, Schema};
 use inherent::inherent;
 
-use crate::ab::{private, FxSeq, Purport, Receptacle, StaticPurport};
+use crate::ab::evolve::{check_incoming, EvolvePlan};
+use crate::ab::{private, Evolve, FxSeq, Purport, Receptacle, SchemaChange, StaticPurport};
 use crate::error::FxResult;
 
 // ================================================================================================
@@ -78,6 +79,42 @@ impl<const W: usize, S: FxSeq> private::InnerReceptacle<true, usize, [S; W]> for
     }
 }
 
+// ================================================================================================
+// Evolve
+//
+// `FxBundles` has a fixed width, hence new columns are rejected.
+// ================================================================================================
+
+impl<const W: usize, S: FxSeq + Clone> Evolve<[S; W]> for FxBundles<W, S> {
+    fn push_evolving(&mut self, data: [S; W], schema: &Schema) -> FxResult<Vec<SchemaChange>> {
+        check_incoming(&data, schema)?;
+
+        if self.data.is_empty() && self.schema.fields.is_empty() {
+            self.schema = schema.clone();
+            self.data.push(data);
+            return Ok(vec![]);
+        }
+
+        let plan = EvolvePlan::try_new(&self.schema, schema, false)?;
+
+        let data = plan.evolve_incoming(data)?;
+        // evolve copies (arrays are shared), hence nothing changes if any cast fails
+        if plan.alters_existing() {
+            let evolved = self
+                .data
+                .iter()
+                .cloned()
+                .map(|e| plan.evolve_existing(e))
+                .collect::<FxResult<Vec<_>>>()?;
+            self.data = evolved;
+        }
+        self.data.push(data);
+        self.schema = plan.schema;
+
+        Ok(plan.changes)
+    }
+}
+
 impl<const W: usize, S: FxSeq> FxBundles<W, S> {
     pub fn new(data: Vec<[S; W]>) -> Self {
         if data.is_empty() {
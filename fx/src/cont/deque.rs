@@ -6,6 +6,7 @@
 use std::ops::{Deref, Range};
 use std::{collections::VecDeque, ops::RangeBounds};
 
+use arrow2::compute::cast::{cast, CastOptions};
 use arrow2::{array::Array, datatypes::DataType};
 
 use super::private::{chop_arr, chop_arr_pieces, concat_arr};
@@ -632,6 +633,29 @@ where
     }
 }
 
+// ================================================================================================
+// Cast
+// ================================================================================================
+
+impl<A> Deque<A>
+where
+    A: AsRef<dyn Array> + From<BoxArr>,
+{
+    /// Casts every array of this [`Deque<A>`] into `datatype`.
+    pub fn try_cast(&self, datatype: &DataType) -> FxResult<Self> {
+        let arrays = self
+            .deque
+            .iter()
+            .map(|a| Ok(cast(a.as_ref(), datatype, CastOptions::default())?.into()))
+            .collect::<FxResult<VecDeque<A>>>()?;
+
+        Ok(Self {
+            datatype: Some(datatype.clone()),
+            deque: arrays,
+        })
+    }
+}
+
 // ================================================================================================
 // Misc
 // ================================================================================================
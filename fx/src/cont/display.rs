@@ -0,0 +1,537 @@
+//! file: display.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 23:58:02 Sunday
+//! brief: Display
+
+use std::any::Any;
+use std::fmt::{self, Display, Write};
+
+use arrow2::array::{get_display, Array, PrimitiveArray};
+use arrow2::datatypes::{DataType, Schema, TimeUnit};
+
+use crate::ab::{Eclectic, FxSeq};
+use crate::cont::{ArcArr, BoxArr, FxBatch, FxBatches, FxBundle, FxTable, FxTabular};
+use crate::value::FxValue;
+
+// ================================================================================================
+// FxDisplay
+// ================================================================================================
+
+/// Default max number of rows to print, the rest is elided in the middle.
+pub const DEFAULT_DISPLAY_ROWS: usize = 10;
+
+/// Default max number of characters of a cell.
+pub const DEFAULT_DISPLAY_WIDTH: usize = 24;
+
+const NULL: &str = "null";
+const ELLIPSIS: &str = "…";
+
+/// A bordered table of a container, with column names & types.
+///
+/// Long data keeps its head & tail rows, long cells are truncated, and floats are printed with
+/// the given precision, or the formatter's one (e.g. `{:.2}`) if not set.
+pub struct FxDisplay<'a> {
+    schema: &'a Schema,
+    columns: Vec<Vec<Cells<'a>>>,
+    max_rows: usize,
+    max_width: usize,
+    float_precision: Option<usize>,
+}
+
+impl<'a> FxDisplay<'a> {
+    fn new(schema: &'a Schema, columns: Vec<Vec<Cells<'a>>>) -> Self {
+        Self {
+            schema,
+            columns,
+            max_rows: DEFAULT_DISPLAY_ROWS,
+            max_width: DEFAULT_DISPLAY_WIDTH,
+            float_precision: None,
+        }
+    }
+
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width.max(1);
+        self
+    }
+
+    pub fn with_float_precision(mut self, precision: usize) -> Self {
+        self.float_precision = Some(precision);
+        self
+    }
+
+    fn num_rows(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|parts| parts.iter().map(Cells::len).sum())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn cell(&self, column: usize, mut row: usize, precision: Option<usize>) -> String {
+        for part in self.columns[column].iter() {
+            if row < part.len() {
+                return part.get(row, precision);
+            }
+            row -= part.len();
+        }
+
+        // columns of different lengths
+        String::new()
+    }
+
+    fn truncate(&self, s: String) -> String {
+        let s = s.replace('\n', "\\n");
+        if s.chars().count() <= self.max_width {
+            return s;
+        }
+
+        let mut res = s.chars().take(self.max_width - 1).collect::<String>();
+        res.push_str(ELLIPSIS);
+        res
+    }
+}
+
+impl Display for FxDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_rows = self.num_rows();
+        writeln!(f, "shape: ({}, {})", num_rows, self.columns.len())?;
+
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+
+        let precision = self.float_precision.or(f.precision());
+        let (head, tail) = if num_rows > self.max_rows {
+            (self.max_rows.div_ceil(2), self.max_rows / 2)
+        } else {
+            (num_rows, 0)
+        };
+        let rows = (0..head)
+            .map(Some)
+            .chain((num_rows > head + tail).then_some(None))
+            .chain((num_rows - tail..num_rows).map(Some));
+
+        let names = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| self.truncate(f.name.clone()))
+            .collect::<Vec<_>>();
+        let types = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| self.truncate(type_name(&f.data_type)))
+            .collect::<Vec<_>>();
+        let body = rows
+            .map(|row| {
+                (0..self.columns.len())
+                    .map(|c| match row {
+                        Some(r) => self.truncate(self.cell(c, r, precision)),
+                        None => ELLIPSIS.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let widths = (0..self.columns.len())
+            .map(|c| {
+                body.iter()
+                    .map(|r| &r[c])
+                    .chain([&names[c], &types[c]])
+                    .map(|s| s.chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let numeric = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| is_numeric(&f.data_type))
+            .collect::<Vec<_>>();
+
+        let border = |f: &mut fmt::Formatter<'_>, c: char| {
+            f.write_char('+')?;
+            for w in widths.iter() {
+                write!(f, "{}+", c.to_string().repeat(w + 2))?;
+            }
+            f.write_char('\n')
+        };
+        let line = |f: &mut fmt::Formatter<'_>, cells: &[String], right: &[bool]| {
+            f.write_char('|')?;
+            for ((cell, w), r) in cells.iter().zip(widths.iter()).zip(right) {
+                // width by chars, since the ellipsis is multi-byte
+                let pad = " ".repeat(w - cell.chars().count());
+                match r {
+                    true => write!(f, " {pad}{cell} |")?,
+                    false => write!(f, " {cell}{pad} |")?,
+                }
+            }
+            f.write_char('\n')
+        };
+
+        let left = vec![false; widths.len()];
+        border(f, '-')?;
+        line(f, &names, &left)?;
+        line(f, &types, &left)?;
+        border(f, '=')?;
+        for row in body.iter() {
+            line(f, row, &numeric)?;
+        }
+        border(f, '-')
+    }
+}
+
+// ================================================================================================
+// Cells
+// ================================================================================================
+
+// a part of a column, i.e. a sequence of a container
+enum Cells<'a> {
+    Arr(&'a dyn Array),
+    Values(Vec<FxValue>),
+}
+
+impl<'a> Cells<'a> {
+    fn from_seq<S: FxSeq + 'static>(seq: &'a S) -> Self {
+        let any = seq as &dyn Any;
+        if let Some(arr) = any.downcast_ref::<ArcArr>() {
+            return Cells::Arr(arr.as_ref());
+        }
+        if let Some(arr) = any.downcast_ref::<BoxArr>() {
+            return Cells::Arr(arr.as_ref());
+        }
+
+        // mutable arrays only support the types of `FxValue`
+        Cells::Values(seq.to_values().unwrap_or_default())
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Cells::Arr(arr) => arr.len(),
+            Cells::Values(values) => values.len(),
+        }
+    }
+
+    fn get(&self, idx: usize, precision: Option<usize>) -> String {
+        match self {
+            Cells::Arr(arr) => {
+                if arr.is_null(idx) {
+                    return NULL.to_string();
+                }
+
+                let any = arr.as_any();
+                if let Some(a) = any.downcast_ref::<PrimitiveArray<f32>>() {
+                    return fmt_float(a.value(idx), precision);
+                }
+                if let Some(a) = any.downcast_ref::<PrimitiveArray<f64>>() {
+                    return fmt_float(a.value(idx), precision);
+                }
+
+                let mut s = String::new();
+                match get_display(*arr, NULL)(&mut s, idx) {
+                    Ok(_) => s,
+                    Err(_) => "?".to_string(),
+                }
+            }
+            Cells::Values(values) => match &values[idx] {
+                FxValue::Bool(v) => v.to_string(),
+                FxValue::I8(v) => v.to_string(),
+                FxValue::I16(v) => v.to_string(),
+                FxValue::I32(v) => v.to_string(),
+                FxValue::I64(v) => v.to_string(),
+                FxValue::U8(v) => v.to_string(),
+                FxValue::U16(v) => v.to_string(),
+                FxValue::U32(v) => v.to_string(),
+                FxValue::U64(v) => v.to_string(),
+                FxValue::F32(v) => fmt_float(*v, precision),
+                FxValue::F64(v) => fmt_float(*v, precision),
+                FxValue::Str(v) => v.clone(),
+                FxValue::Null => NULL.to_string(),
+            },
+        }
+    }
+}
+
+fn fmt_float<T: Display>(v: T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{v:.p$}"),
+        None => v.to_string(),
+    }
+}
+
+fn type_name(data_type: &DataType) -> String {
+    let unit = |u: &TimeUnit| match u {
+        TimeUnit::Second => "s",
+        TimeUnit::Millisecond => "ms",
+        TimeUnit::Microsecond => "us",
+        TimeUnit::Nanosecond => "ns",
+    };
+
+    match data_type {
+        DataType::Null => "null".to_string(),
+        DataType::Boolean => "bool".to_string(),
+        DataType::Int8 => "i8".to_string(),
+        DataType::Int16 => "i16".to_string(),
+        DataType::Int32 => "i32".to_string(),
+        DataType::Int64 => "i64".to_string(),
+        DataType::UInt8 => "u8".to_string(),
+        DataType::UInt16 => "u16".to_string(),
+        DataType::UInt32 => "u32".to_string(),
+        DataType::UInt64 => "u64".to_string(),
+        DataType::Float16 => "f16".to_string(),
+        DataType::Float32 => "f32".to_string(),
+        DataType::Float64 => "f64".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 => "str".to_string(),
+        DataType::Binary | DataType::LargeBinary => "bin".to_string(),
+        DataType::Date32 | DataType::Date64 => "date".to_string(),
+        DataType::Timestamp(u, None) => format!("ts[{}]", unit(u)),
+        DataType::Timestamp(u, Some(tz)) => format!("ts[{}, {tz}]", unit(u)),
+        DataType::Decimal(p, s) => format!("dec({p}, {s})"),
+        other => format!("{other:?}"),
+    }
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal(_, _)
+    )
+}
+
+// ================================================================================================
+// impl Display
+// ================================================================================================
+
+impl FxBatch {
+    pub fn display(&self) -> FxDisplay<'_> {
+        let columns = self
+            .data
+            .arrays()
+            .iter()
+            .map(|a| vec![Cells::Arr(a.as_ref())])
+            .collect();
+
+        FxDisplay::new(self.schema(), columns)
+    }
+}
+
+impl<const W: usize, S: FxSeq + 'static> FxBundle<W, S> {
+    pub fn display(&self) -> FxDisplay<'_> {
+        let columns = self
+            .sequences()
+            .iter()
+            .map(|s| vec![Cells::from_seq(s)])
+            .collect();
+
+        FxDisplay::new(self.schema(), columns)
+    }
+}
+
+impl<E: Eclectic> FxBatches<E>
+where
+    E::Seq: 'static,
+{
+    pub fn display(&self) -> FxDisplay<'_> {
+        let columns = (0..self.schema.fields.len())
+            .map(|c| {
+                self.data
+                    .iter()
+                    .filter_map(|e| e.sequences().get(c).map(Cells::from_seq))
+                    .collect()
+            })
+            .collect();
+
+        FxDisplay::new(&self.schema, columns)
+    }
+}
+
+impl<const W: usize> FxTable<W> {
+    pub fn display(&self) -> FxDisplay<'_> {
+        let columns = self
+            .ref_data()
+            .iter()
+            .map(|dq| dq.iter().map(|a| Cells::Arr(a.as_ref())).collect())
+            .collect();
+
+        FxDisplay::new(self.schema(), columns)
+    }
+}
+
+impl FxTabular {
+    pub fn display(&self) -> FxDisplay<'_> {
+        let columns = self
+            .ref_data()
+            .iter()
+            .map(|dq| dq.iter().map(|a| Cells::Arr(a.as_ref())).collect())
+            .collect();
+
+        FxDisplay::new(self.schema(), columns)
+    }
+}
+
+impl Display for FxBatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl Display for FxTabular {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl<const W: usize> Display for FxTable<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl<const W: usize, S: FxSeq + 'static> Display for FxBundle<W, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl<E: Eclectic> Display for FxBatches<E>
+where
+    E::Seq: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_display {
+    use super::*;
+    use crate::ab::{Dqs, FromSlice};
+    use crate::arc_arr;
+    use crate::cont::{ArcVec, ChunkArr};
+
+    #[test]
+    fn batch_display_success() {
+        let batch = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1), None, Some(300)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+                arc_arr!([1.5, 2.25, 3.0]),
+            ],
+            ["c1", "c2", "c3"],
+        );
+
+        let expected = "\
+shape: (3, 3)
++------+------+------+
+| c1   | c2   | c3   |
+| i32  | str  | f64  |
++======+======+======+
+|    1 | a    |  1.5 |
+| null | b    | 2.25 |
+|  300 | null |    3 |
++------+------+------+
+";
+        assert_eq!(batch.to_string(), expected);
+        println!("{batch}");
+
+        // precision from the formatter
+        let res = format!("{batch:.2}");
+        assert!(res.contains("| 1.50 |") && res.contains("| 3.00 |"));
+
+        // precision from the options wins
+        let res = batch.display().with_float_precision(1).to_string();
+        assert!(res.contains("| 1.5 |") && res.contains("| 2.2 |"));
+    }
+
+    #[test]
+    fn display_truncation_success() {
+        let batch = FxBatch::new_with_names(
+            vec![
+                arc_arr!((0..100).collect::<Vec<i64>>()),
+                arc_arr!(["abcdefghijklmnopqrstuvwxyz"; 100]),
+            ],
+            ["c1", "c2"],
+        );
+
+        let res = batch
+            .display()
+            .with_max_rows(4)
+            .with_max_width(8)
+            .to_string();
+        println!("{res}");
+
+        let lines = res.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "shape: (100, 2)");
+        // 3 borders, 2 headers, 2 heads, 1 ellipsis & 2 tails
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[5], "|   0 | abcdefg… |");
+        assert_eq!(lines[7], "|   … | …        |");
+        assert_eq!(lines[9], "|  99 | abcdefg… |");
+
+        // no rows
+        let res = batch.display().with_max_rows(0).to_string();
+        assert_eq!(res.lines().count(), 7);
+    }
+
+    #[test]
+    fn containers_display_success() {
+        // bundle of arrays & vectors
+        let bundle =
+            FxBundle::new_with_names([arc_arr!([1, 2]), arc_arr!([Some("x"), None])], ["a", "b"]);
+        assert!(bundle.to_string().contains("|   2 | null |"));
+
+        let bundle = FxBundle::new_with_names(
+            [
+                ArcVec::from_slice([Some(1.5f32), None]),
+                ArcVec::from_slice([Some("y"), None]),
+            ],
+            ["a", "b"],
+        );
+        println!("{bundle:.3}");
+        assert!(format!("{bundle:.3}").contains("| 1.500 | y    |"));
+
+        // batches
+        let ca = ChunkArr::try_new(vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])]).unwrap();
+        let cb = ChunkArr::try_new(vec![arc_arr!([3]), arc_arr!(["c"])]).unwrap();
+        let batches = FxBatches::new_with_names(vec![ca, cb], ["c1", "c2"]);
+        let res = batches.to_string();
+        println!("{res}");
+        assert!(res.starts_with("shape: (3, 2)"));
+        assert!(res.contains("|   3 | c   |"));
+
+        // tabular & table
+        let mut tabular = FxTabular::new(vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])]);
+        tabular
+            .deque_push_back(vec![arc_arr!([3, 4]), arc_arr!(["c", "d"])])
+            .unwrap();
+        let res = tabular.to_string();
+        println!("{res}");
+        assert!(res.starts_with("shape: (4, 2)"));
+        assert!(res.contains("|     4 | d     |"));
+
+        let table = FxTable::<2>::try_from(tabular).unwrap();
+        assert_eq!(table.to_string(), res);
+    }
+}
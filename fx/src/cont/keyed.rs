@@ -0,0 +1,642 @@
+//! file: keyed.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 17:05:12 Sunday
+//! brief: Keyed batches
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Bound, RangeBounds};
+
+use arrow2::array::PrimitiveArray;
+use arrow2::compute::concatenate::concatenate;
+use arrow2::compute::take::take;
+use arrow2::datatypes::{DataType, Field, Schema};
+use inherent::inherent;
+
+use crate::ab::*;
+use crate::cont::{ArcArr, ChunkArr, FxBatch};
+use crate::error::{FxError, FxResult};
+use crate::value::{FxValue, OrdValue};
+
+// ================================================================================================
+// RowPos & IndexKind
+// ================================================================================================
+
+/// Position of a row in [`FxKeyedBatches`]: the chunk's index and the row's index in the chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RowPos {
+    pub chunk: usize,
+    pub row: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexKind {
+    /// equality lookup only
+    Hash,
+    /// equality & range lookup
+    Ordered,
+}
+
+// keys & indexed values of each row in a chunk, computed before touching the container so that a
+// failing conversion leaves it unchanged
+type ChunkEntries = (Vec<Vec<OrdValue>>, Vec<(usize, Vec<FxValue>)>);
+
+#[derive(Debug, Clone)]
+enum SecondaryIndex {
+    Hash(HashMap<OrdValue, Vec<RowPos>>),
+    Ordered(BTreeMap<OrdValue, Vec<RowPos>>),
+}
+
+impl SecondaryIndex {
+    fn new(kind: IndexKind) -> Self {
+        match kind {
+            IndexKind::Hash => SecondaryIndex::Hash(HashMap::new()),
+            IndexKind::Ordered => SecondaryIndex::Ordered(BTreeMap::new()),
+        }
+    }
+
+    fn kind(&self) -> IndexKind {
+        match self {
+            SecondaryIndex::Hash(_) => IndexKind::Hash,
+            SecondaryIndex::Ordered(_) => IndexKind::Ordered,
+        }
+    }
+
+    fn insert(&mut self, value: FxValue, pos: RowPos) {
+        let value = OrdValue(value);
+        match self {
+            SecondaryIndex::Hash(m) => m.entry(value).or_default().push(pos),
+            SecondaryIndex::Ordered(m) => m.entry(value).or_default().push(pos),
+        }
+    }
+
+    fn get(&self, value: &FxValue) -> &[RowPos] {
+        let value = OrdValue(value.clone());
+        let res = match self {
+            SecondaryIndex::Hash(m) => m.get(&value),
+            SecondaryIndex::Ordered(m) => m.get(&value),
+        };
+
+        res.map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn clear(&mut self) {
+        match self {
+            SecondaryIndex::Hash(m) => m.clear(),
+            SecondaryIndex::Ordered(m) => m.clear(),
+        }
+    }
+}
+
+// ================================================================================================
+// FxKeyedBatches
+//
+// A `Receptacle` whose rows are identified by a key derived from one or more columns. Besides
+// the (unique) key, secondary indexes can be built on any column, so that matching rows are
+// located across all stored chunks without a full scan.
+//
+// Chunks are only exposed by reference, mutating a chunk in place would invalidate the indexes.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct FxKeyedBatches<E: Eclectic> {
+    pub(crate) schema: Schema,
+    key_columns: Vec<usize>,
+    data: Vec<E>,
+    keys: HashMap<Vec<OrdValue>, RowPos>,
+    indexes: HashMap<usize, SecondaryIndex>,
+}
+
+// ================================================================================================
+// Purport
+// ================================================================================================
+
+impl<E: Eclectic> StaticPurport for FxKeyedBatches<E> {}
+
+#[inherent]
+impl<E: Eclectic> Purport for FxKeyedBatches<E> {
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+// ================================================================================================
+// Receptacle's impl
+// ================================================================================================
+
+impl<E> private::InnerReceptacle<true, usize, E> for FxKeyedBatches<E>
+where
+    E: Eclectic<Seq = ArcArr> + Confined,
+{
+    type OutRef<'a> = &'a E where Self: 'a;
+
+    type OutMut<'a> = &'a E where Self: 'a;
+
+    fn new_empty() -> Self {
+        Self {
+            schema: Schema::from(Vec::<Field>::new()),
+            key_columns: Vec::new(),
+            data: Vec::new(),
+            keys: HashMap::new(),
+            indexes: HashMap::new(),
+        }
+    }
+
+    fn ref_schema(&self) -> Option<&Schema> {
+        Some(&self.schema)
+    }
+
+    fn get_chunk(&self, key: usize) -> FxResult<Self::OutRef<'_>> {
+        self.data.get_chunk(key)
+    }
+
+    fn get_mut_chunk(&mut self, key: usize) -> FxResult<Self::OutMut<'_>> {
+        self.data.get_chunk(key)
+    }
+
+    fn insert_chunk_type_unchecked(&mut self, key: usize, data: E) -> FxResult<()> {
+        if key > self.data.len() {
+            return Err(FxError::OutBounds);
+        }
+        let new_entries = self.chunk_entries(&data)?;
+        self.check_new_keys(&new_entries.0)?;
+
+        let mut entries = self.all_entries()?;
+        entries.insert(key, new_entries);
+        self.data.insert_chunk_type_unchecked(key, data)?;
+        self.reindex(entries);
+
+        Ok(())
+    }
+
+    fn remove_chunk(&mut self, key: usize) -> FxResult<()> {
+        if key >= self.data.len() {
+            return Err(FxError::OutBounds);
+        }
+        let mut entries = self.all_entries()?;
+        entries.remove(key);
+        self.data.remove_chunk(key)?;
+        self.reindex(entries);
+
+        Ok(())
+    }
+
+    fn push_chunk_type_unchecked(&mut self, data: E) -> FxResult<()> {
+        let entries = self.chunk_entries(&data)?;
+        self.check_new_keys(&entries.0)?;
+
+        let chunk = self.data.len();
+        self.index_chunk(chunk, entries);
+        self.data.push(data);
+
+        Ok(())
+    }
+
+    fn pop_chunk(&mut self) -> FxResult<()> {
+        let mut entries = self.all_entries()?;
+        entries.pop();
+        self.data.pop_chunk()?;
+        self.reindex(entries);
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// Indexing
+// ================================================================================================
+
+impl<E> FxKeyedBatches<E>
+where
+    E: Eclectic<Seq = ArcArr> + Confined,
+{
+    fn column_position(&self, name: &str) -> FxResult<usize> {
+        self.schema
+            .fields
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| FxError::InvalidArgument(format!("column `{name}` not found")))
+    }
+
+    // only the types convertible by `to_values` can be keyed or indexed
+    fn check_indexable(&self, col: usize) -> FxResult<()> {
+        let field = &self.schema.fields[col];
+        if is_indexable(&field.data_type) {
+            Ok(())
+        } else {
+            Err(FxError::InvalidArgument(format!(
+                "column `{}` of {:?} cannot be indexed",
+                field.name, field.data_type
+            )))
+        }
+    }
+
+    fn column_values(data: &E, idx: usize) -> FxResult<Vec<FxValue>> {
+        data.ref_sequences()
+            .get(idx)
+            .ok_or(FxError::OutBounds)?
+            .to_values()
+    }
+
+    // keys of each row in a chunk
+    fn chunk_keys(&self, data: &E) -> FxResult<Vec<Vec<OrdValue>>> {
+        let len = data.max_len().unwrap_or(0);
+        let mut keys = vec![Vec::with_capacity(self.key_columns.len()); len];
+
+        for &k in self.key_columns.iter() {
+            let values = Self::column_values(data, k)?;
+            if values.len() != len {
+                return Err(FxError::LengthMismatch(values.len(), len));
+            }
+            for (key, v) in keys.iter_mut().zip(values) {
+                key.push(OrdValue(v));
+            }
+        }
+
+        Ok(keys)
+    }
+
+    // make sure keys are neither duplicated among themselves nor with the existing ones
+    fn check_new_keys(&self, keys: &[Vec<OrdValue>]) -> FxResult<()> {
+        let mut seen = HashSet::with_capacity(keys.len());
+        for k in keys.iter() {
+            if self.keys.contains_key(k) || !seen.insert(k) {
+                return Err(FxError::InvalidArgument(format!("duplicated key {k:?}")));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn chunk_entries(&self, data: &E) -> FxResult<ChunkEntries> {
+        let keys = self.chunk_keys(data)?;
+        let values = self
+            .indexes
+            .keys()
+            .map(|&col| Self::column_values(data, col).map(|v| (col, v)))
+            .collect::<FxResult<Vec<_>>>()?;
+
+        Ok((keys, values))
+    }
+
+    fn all_entries(&self) -> FxResult<Vec<ChunkEntries>> {
+        self.data.iter().map(|d| self.chunk_entries(d)).collect()
+    }
+
+    fn index_chunk(&mut self, chunk: usize, (keys, values): ChunkEntries) {
+        for (row, k) in keys.into_iter().enumerate() {
+            self.keys.insert(k, RowPos { chunk, row });
+        }
+
+        for (col, vals) in values {
+            if let Some(index) = self.indexes.get_mut(&col) {
+                for (row, v) in vals.into_iter().enumerate() {
+                    index.insert(v, RowPos { chunk, row });
+                }
+            }
+        }
+    }
+
+    // chunk positions are shifted by `insert` & `remove`, hence all the indexes are rebuilt
+    fn reindex(&mut self, entries: Vec<ChunkEntries>) {
+        self.keys.clear();
+        self.indexes.values_mut().for_each(SecondaryIndex::clear);
+
+        for (chunk, e) in entries.into_iter().enumerate() {
+            self.index_chunk(chunk, e);
+        }
+    }
+
+    fn take_rows(&self, positions: &[RowPos]) -> FxResult<FxBatch> {
+        if positions.is_empty() {
+            return Ok(FxBatch::empty_with_schema(self.schema.clone()));
+        }
+
+        // consecutive positions of the same chunk are taken at once
+        let mut pieces: Vec<Vec<ArcArr>> = Vec::new();
+        for run in positions.chunk_by(|a, b| a.chunk == b.chunk) {
+            let seqs = self
+                .data
+                .get(run[0].chunk)
+                .ok_or(FxError::OutBounds)?
+                .ref_sequences();
+            let indices =
+                PrimitiveArray::<u32>::from_vec(run.iter().map(|p| p.row as u32).collect());
+            let taken = seqs
+                .iter()
+                .map(|s| take(s.as_ref(), &indices).map(ArcArr::from))
+                .collect::<Result<Vec<_>, _>>()?;
+            pieces.push(taken);
+        }
+
+        let arrays = (0..self.schema.fields.len())
+            .map(|col| {
+                let arrs = pieces.iter().map(|p| p[col].as_ref()).collect::<Vec<_>>();
+                concatenate(&arrs).map(ArcArr::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FxBatch {
+            schema: self.schema.clone(),
+            data: ChunkArr::try_new(arrays)?,
+        })
+    }
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+impl<E> FxKeyedBatches<E>
+where
+    E: Eclectic<Seq = ArcArr> + Confined,
+{
+    /// Creates an empty container, whose key is made of the columns named by `keys`.
+    pub fn try_new<I, T>(schema: Schema, keys: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut res = <Self as private::InnerReceptacle<true, usize, E>>::new_empty();
+        res.schema = schema;
+        res.key_columns = keys
+            .into_iter()
+            .map(|k| res.column_position(k.as_ref()))
+            .collect::<FxResult<Vec<_>>>()?;
+
+        if res.key_columns.is_empty() {
+            return Err(FxError::InvalidArgument("empty key columns".to_string()));
+        }
+        for &k in res.key_columns.iter() {
+            res.check_indexable(k)?;
+        }
+
+        Ok(res)
+    }
+
+    pub fn key_names(&self) -> Vec<&str> {
+        self.key_columns
+            .iter()
+            .map(|&k| self.schema.fields[k].name.as_str())
+            .collect()
+    }
+
+    pub fn data(&self) -> &[E] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<E> {
+        self.data
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Builds a secondary index on `column`, existing rows are indexed immediately. Building an
+    /// index on an already indexed column replaces the old one.
+    pub fn add_index(&mut self, column: &str, kind: IndexKind) -> FxResult<()> {
+        let col = self.column_position(column)?;
+        self.check_indexable(col)?;
+
+        let mut index = SecondaryIndex::new(kind);
+        for (chunk, d) in self.data.iter().enumerate() {
+            for (row, v) in Self::column_values(d, col)?.into_iter().enumerate() {
+                index.insert(v, RowPos { chunk, row });
+            }
+        }
+        self.indexes.insert(col, index);
+
+        Ok(())
+    }
+
+    pub fn drop_index(&mut self, column: &str) -> FxResult<()> {
+        let col = self.column_position(column)?;
+        self.indexes.remove(&col).map(|_| ()).ok_or(FxError::NoKey)
+    }
+
+    pub fn index_kind(&self, column: &str) -> Option<IndexKind> {
+        let col = self.column_position(column).ok()?;
+        self.indexes.get(&col).map(SecondaryIndex::kind)
+    }
+
+    pub fn contains_key(&self, key: &[FxValue]) -> bool {
+        self.position(key).is_some()
+    }
+
+    pub fn position(&self, key: &[FxValue]) -> Option<RowPos> {
+        let key = key.iter().cloned().map(OrdValue).collect::<Vec<_>>();
+        self.keys.get(&key).copied()
+    }
+
+    /// Returns the row identified by `key`.
+    pub fn get_by_key(&self, key: &[FxValue]) -> FxResult<FxBatch> {
+        let pos = self.position(key).ok_or(FxError::NoKey)?;
+        self.take_rows(&[pos])
+    }
+
+    /// Returns all rows whose `column` equals to `value`, the column must be indexed.
+    pub fn lookup(&self, column: &str, value: &FxValue) -> FxResult<FxBatch> {
+        let col = self.column_position(column)?;
+        let index = self.indexes.get(&col).ok_or_else(|| {
+            FxError::InvalidOperation(format!("column `{column}` is not indexed"))
+        })?;
+
+        self.take_rows(index.get(value))
+    }
+
+    /// Returns all rows whose `column` falls in `range`, ordered by the column's value.
+    /// The column must be indexed by [`IndexKind::Ordered`].
+    pub fn lookup_range<R>(&self, column: &str, range: R) -> FxResult<FxBatch>
+    where
+        R: RangeBounds<FxValue>,
+    {
+        let col = self.column_position(column)?;
+        match self.indexes.get(&col) {
+            Some(SecondaryIndex::Ordered(m)) => {
+                let bounds: (Bound<OrdValue>, Bound<OrdValue>) = (
+                    range.start_bound().cloned().map(OrdValue),
+                    range.end_bound().cloned().map(OrdValue),
+                );
+                let positions = m
+                    .range(bounds)
+                    .flat_map(|(_, p)| p)
+                    .copied()
+                    .collect::<Vec<_>>();
+                self.take_rows(&positions)
+            }
+            _ => Err(FxError::InvalidOperation(format!(
+                "column `{column}` is not indexed by `IndexKind::Ordered`"
+            ))),
+        }
+    }
+}
+
+// ================================================================================================
+// Helpers
+// ================================================================================================
+
+fn is_indexable(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Utf8
+    )
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_keyed {
+    use super::*;
+
+    use crate::ab::FromSlice;
+
+    fn gen_batch(ids: [i32; 3], syms: [&str; 3], prices: [f64; 3]) -> FxBatch {
+        FxBatch::new_with_names(
+            vec![
+                ArcArr::from_slice(ids),
+                ArcArr::from_slice(syms),
+                ArcArr::from_slice(prices),
+            ],
+            ["id", "sym", "price"],
+        )
+    }
+
+    #[test]
+    fn keyed_lookup_success() {
+        let b1 = gen_batch([1, 2, 3], ["a", "b", "a"], [1.0, 2.0, 3.0]);
+        let b2 = gen_batch([4, 5, 6], ["c", "a", "b"], [4.0, 5.0, 6.0]);
+
+        let mut kb = FxKeyedBatches::<FxBatch>::try_new(b1.schema().clone(), ["id"]).unwrap();
+        kb.push(b1).unwrap();
+        kb.add_index("sym", IndexKind::Hash).unwrap();
+        kb.push(b2).unwrap();
+
+        assert_eq!(kb.len(), 6);
+        assert_eq!(
+            kb.position(&[FxValue::I32(5)]),
+            Some(RowPos { chunk: 1, row: 1 })
+        );
+
+        let res = kb.lookup("sym", &FxValue::from("a")).unwrap();
+        println!("{res:?}");
+        assert_eq!(res.schema().fields[0].name, "id");
+        assert_eq!(
+            res.sequences()[0].to_values().unwrap(),
+            vec![FxValue::I32(1), FxValue::I32(3), FxValue::I32(5)]
+        );
+
+        let res = kb.lookup("sym", &FxValue::from("z")).unwrap();
+        assert_eq!(res.max_len(), Some(0));
+
+        assert!(kb.lookup("price", &FxValue::F64(1.0)).is_err());
+
+        let res = kb.get_by_key(&[FxValue::I32(6)]).unwrap();
+        assert_eq!(
+            res.sequences()[1].to_values().unwrap(),
+            vec![FxValue::from("b")]
+        );
+    }
+
+    #[test]
+    fn keyed_range_and_removal_success() {
+        let b1 = gen_batch([1, 2, 3], ["a", "b", "a"], [1.0, 2.0, 3.0]);
+        let b2 = gen_batch([4, 5, 6], ["c", "a", "b"], [4.0, 5.0, 6.0]);
+
+        let mut kb =
+            FxKeyedBatches::<FxBatch>::try_new(b1.schema().clone(), ["sym", "id"]).unwrap();
+        kb.add_index("price", IndexKind::Ordered).unwrap();
+        kb.extend([b1, b2]).unwrap();
+
+        let res = kb
+            .lookup_range("price", FxValue::F64(2.5)..FxValue::F64(5.0))
+            .unwrap();
+        assert_eq!(
+            res.sequences()[0].to_values().unwrap(),
+            vec![FxValue::I32(3), FxValue::I32(4)]
+        );
+
+        // duplicated key is rejected
+        let dup = gen_batch([7, 8, 1], ["x", "y", "a"], [7.0, 8.0, 9.0]);
+        assert!(kb.push(dup).is_err());
+        assert_eq!(kb.len(), 6);
+
+        // removing a chunk shifts the positions of the following ones
+        kb.remove(0).unwrap();
+        assert_eq!(kb.len(), 3);
+        assert!(!kb.contains_key(&[FxValue::from("a"), FxValue::I32(1)]));
+        assert_eq!(
+            kb.position(&[FxValue::from("a"), FxValue::I32(5)]),
+            Some(RowPos { chunk: 0, row: 1 })
+        );
+
+        let res = kb.lookup_range("price", ..).unwrap();
+        assert_eq!(res.max_len(), Some(3));
+    }
+
+    #[test]
+    fn keyed_nan_lookup_success() {
+        // `FxValue` keeps the IEEE semantics, while indexes still find `NaN`
+        assert_ne!(FxValue::F64(f64::NAN), FxValue::F64(f64::NAN));
+        assert_eq!(FxValue::F64(-0.0), FxValue::F64(0.0));
+
+        let b = gen_batch([1, 2, 3], ["a", "b", "c"], [1.0, f64::NAN, 3.0]);
+        let mut kb = FxKeyedBatches::<FxBatch>::try_new(b.schema().clone(), ["price"]).unwrap();
+        kb.add_index("price", IndexKind::Hash).unwrap();
+        kb.push(b).unwrap();
+
+        assert!(kb.contains_key(&[FxValue::F64(f64::NAN)]));
+        assert!(kb.contains_key(&[FxValue::F64(-f64::NAN)]));
+        assert!(kb.contains_key(&[FxValue::F64(f64::from_bits(f64::NAN.to_bits() | 1))]));
+        let res = kb.lookup("price", &FxValue::F64(f64::NAN)).unwrap();
+        assert_eq!(
+            res.sequences()[0].to_values().unwrap(),
+            vec![FxValue::I32(2)]
+        );
+
+        // `-0.0` & `0.0` are the same key
+        let b = gen_batch([4, 5, 6], ["d", "e", "f"], [-0.0, 5.0, 6.0]);
+        kb.push(b).unwrap();
+        assert!(kb.contains_key(&[FxValue::F64(0.0)]));
+        let dup = gen_batch([7, 8, 9], ["g", "h", "i"], [0.0, 8.0, 9.0]);
+        assert!(kb.push(dup).is_err());
+    }
+
+    #[test]
+    fn keyed_unindexable_column_failed() {
+        let days = PrimitiveArray::<i32>::from_vec(vec![19000, 19001, 19002]).to(DataType::Date32);
+        let b = FxBatch::new_with_names(
+            vec![ArcArr::from_slice([1, 2, 3]), ArcArr::from(days.boxed())],
+            ["id", "d"],
+        );
+
+        assert!(FxKeyedBatches::<FxBatch>::try_new(b.schema().clone(), ["d"]).is_err());
+
+        let mut kb = FxKeyedBatches::<FxBatch>::try_new(b.schema().clone(), ["id"]).unwrap();
+        assert!(kb.add_index("d", IndexKind::Hash).is_err());
+        assert_eq!(kb.index_kind("d"), None);
+
+        // the rejected index does not break later pushes
+        kb.push(b).unwrap();
+        assert_eq!(kb.len(), 3);
+        assert_eq!(kb.data().len(), 1);
+        assert!(kb.contains_key(&[FxValue::I32(2)]));
+    }
+}
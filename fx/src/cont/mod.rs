@@ -9,9 +9,13 @@ pub mod batches;
 pub mod bundle;
 pub mod bundles;
 pub mod deque;
+pub mod display;
 pub mod ext;
+pub mod keyed;
 pub mod nullopt;
+pub mod partitioned;
 mod private;
+pub mod shared;
 pub mod table;
 pub mod tabular;
 
@@ -20,7 +24,11 @@ pub use batches::*;
 pub use bundle::*;
 pub use bundles::*;
 pub use deque::*;
+pub use display::*;
 pub use ext::*;
+pub use keyed::*;
 pub use nullopt::*;
+pub use partitioned::*;
+pub use shared::*;
 pub use table::*;
 pub use tabular::*;
@@ -0,0 +1,356 @@
+//! file: partitioned.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 19:12:40 Sunday
+//! brief: Partitioned batches
+
+use std::collections::HashMap;
+
+use arrow2::compute::concatenate::concatenate;
+use arrow2::datatypes::{Field, Schema};
+use inherent::inherent;
+
+use crate::ab::*;
+use crate::cont::{ArcArr, ChunkArr, FxBatch, FxBatches};
+use crate::error::{FxError, FxResult};
+use crate::value::{FxValue, OrdValue};
+
+// ================================================================================================
+// PartitionKey
+// ================================================================================================
+
+/// Values of the partition columns, which identify a partition. Unlike [`FxValue`], floats are
+/// compared by `total_cmp`, hence every key (`NaN` included) can be looked up.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PartitionKey(Vec<OrdValue>);
+
+impl PartitionKey {
+    pub fn values(&self) -> Vec<&FxValue> {
+        self.0.iter().map(|v| &v.0).collect()
+    }
+
+    pub fn into_values(self) -> Vec<FxValue> {
+        self.0.into_iter().map(|v| v.0).collect()
+    }
+}
+
+impl From<Vec<FxValue>> for PartitionKey {
+    fn from(values: Vec<FxValue>) -> Self {
+        PartitionKey(values.into_iter().map(OrdValue).collect())
+    }
+}
+
+impl From<&[FxValue]> for PartitionKey {
+    fn from(values: &[FxValue]) -> Self {
+        PartitionKey(values.iter().cloned().map(OrdValue).collect())
+    }
+}
+
+/// Lookup of a partition map by plain values, e.g. `parts.get_by_key(&["a".into()])`.
+pub trait PartitionMap<V> {
+    fn get_by_key(&self, key: &[FxValue]) -> Option<&V>;
+}
+
+impl<V> PartitionMap<V> for HashMap<PartitionKey, V> {
+    fn get_by_key(&self, key: &[FxValue]) -> Option<&V> {
+        self.get(&PartitionKey::from(key))
+    }
+}
+
+// ================================================================================================
+// FxPartitioned
+//
+// A `Receptacle` keyed by partition values. Every pushed chunk is split by the partition columns,
+// and its rows are routed to the sub-container of the corresponding partition.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct FxPartitioned {
+    pub(crate) schema: Schema,
+    columns: Vec<usize>,
+    data: HashMap<PartitionKey, FxBatches<FxBatch>>,
+}
+
+// ================================================================================================
+// Purport
+// ================================================================================================
+
+impl StaticPurport for FxPartitioned {}
+
+#[inherent]
+impl Purport for FxPartitioned {
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+// ================================================================================================
+// Receptacle's impl
+// ================================================================================================
+
+impl<E> private::InnerReceptacle<true, PartitionKey, E> for FxPartitioned
+where
+    E: Eclectic<Seq = ArcArr> + Confined,
+{
+    type OutRef<'a> = &'a FxBatches<FxBatch> where Self: 'a;
+
+    type OutMut<'a> = &'a mut FxBatches<FxBatch> where Self: 'a;
+
+    fn new_empty() -> Self {
+        Self {
+            schema: Schema::from(Vec::<Field>::new()),
+            columns: Vec::new(),
+            data: HashMap::new(),
+        }
+    }
+
+    fn ref_schema(&self) -> Option<&Schema> {
+        Some(&self.schema)
+    }
+
+    fn get_chunk(&self, key: PartitionKey) -> FxResult<Self::OutRef<'_>> {
+        self.data.get(&key).ok_or(FxError::NoKey)
+    }
+
+    fn get_mut_chunk(&mut self, key: PartitionKey) -> FxResult<Self::OutMut<'_>> {
+        self.data.get_mut(&key).ok_or(FxError::NoKey)
+    }
+
+    // all rows of `data` are required to belong to the partition `key`
+    fn insert_chunk_type_unchecked(&mut self, key: PartitionKey, data: E) -> FxResult<()> {
+        let parts = data.partition_by(&self.columns)?;
+        if parts.keys().any(|k| k != &key) {
+            return Err(FxError::InvalidArgument(format!(
+                "rows do not belong to the partition {key:?}"
+            )));
+        }
+
+        self.route(parts);
+
+        Ok(())
+    }
+
+    fn remove_chunk(&mut self, key: PartitionKey) -> FxResult<()> {
+        self.data.remove(&key).map(|_| ()).ok_or(FxError::NoKey)
+    }
+
+    fn push_chunk_type_unchecked(&mut self, data: E) -> FxResult<()> {
+        let parts = data.partition_by(&self.columns)?;
+        self.route(parts);
+
+        Ok(())
+    }
+
+    fn pop_chunk(&mut self) -> FxResult<()> {
+        Err(FxError::InvalidOperation(
+            "partitions are unordered, use `remove` instead".to_string(),
+        ))
+    }
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+impl FxPartitioned {
+    /// Creates an empty container, partitioned by the columns named by `columns`.
+    pub fn try_new<I, T>(schema: Schema, columns: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| {
+                let c = c.as_ref();
+                schema
+                    .fields
+                    .iter()
+                    .position(|f| f.name == c)
+                    .ok_or_else(|| FxError::InvalidArgument(format!("column `{c}` not found")))
+            })
+            .collect::<FxResult<Vec<_>>>()?;
+
+        if columns.is_empty() {
+            return Err(FxError::InvalidArgument(
+                "empty partition columns".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            schema,
+            columns,
+            data: HashMap::new(),
+        })
+    }
+
+    fn route(&mut self, parts: HashMap<PartitionKey, FxBatch>) {
+        for (key, mut batch) in parts {
+            batch.schema = self.schema.clone();
+            self.data
+                .entry(key)
+                .or_insert_with(|| FxBatches::empty_with_schema(self.schema.clone()))
+                .data
+                .push(batch);
+        }
+    }
+
+    pub fn partition_names(&self) -> Vec<&str> {
+        self.columns
+            .iter()
+            .map(|&c| self.schema.fields[c].name.as_str())
+            .collect()
+    }
+
+    /// Partition keys in ascending order.
+    pub fn keys(&self) -> Vec<&PartitionKey> {
+        let mut keys = self.data.keys().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Returns the partition identified by the values of the partition columns.
+    pub fn get_by_key(&self, key: &[FxValue]) -> FxResult<&FxBatches<FxBatch>> {
+        self.data.get_by_key(key).ok_or(FxError::NoKey)
+    }
+
+    pub fn partitions(&self) -> &HashMap<PartitionKey, FxBatches<FxBatch>> {
+        &self.data
+    }
+
+    pub fn into_partitions(self) -> HashMap<PartitionKey, FxBatches<FxBatch>> {
+        self.data
+    }
+
+    /// Number of partitions.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Collects all partitions (by ascending key) back into one container.
+    pub fn into_batches(self) -> FxBatches<FxBatch> {
+        let mut data = self.data.into_iter().collect::<Vec<_>>();
+        data.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let data = data.into_iter().flat_map(|(_, b)| b.data).collect();
+
+        FxBatches {
+            schema: self.schema,
+            data,
+        }
+    }
+
+    /// Concatenates all partitions (by ascending key) into one [`FxBatch`].
+    pub fn concat(&self) -> FxResult<FxBatch> {
+        let batches = self
+            .keys()
+            .into_iter()
+            .flat_map(|k| self.data[k].data.iter())
+            .collect::<Vec<_>>();
+
+        if batches.is_empty() {
+            return Ok(FxBatch::empty_with_schema(self.schema.clone()));
+        }
+
+        let arrays = (0..self.schema.fields.len())
+            .map(|col| {
+                let arrs = batches
+                    .iter()
+                    .map(|b| b.data[col].as_ref())
+                    .collect::<Vec<_>>();
+                concatenate(&arrs).map(ArcArr::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FxBatch {
+            schema: self.schema.clone(),
+            data: ChunkArr::try_new(arrays)?,
+        })
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_partitioned {
+    use super::*;
+
+    use crate::ab::FromSlice;
+
+    fn gen_batch() -> FxBatch {
+        FxBatch::new_with_names(
+            vec![
+                ArcArr::from_slice(["a", "b", "a", "c", "b"]),
+                ArcArr::from_slice([1i32, 2, 3, 4, 5]),
+                ArcArr::from_slice([1.0f64, 2.0, 3.0, 4.0, 5.0]),
+            ],
+            ["sym", "id", "price"],
+        )
+    }
+
+    #[test]
+    fn partition_by_success() {
+        let batch = gen_batch();
+
+        let parts = batch.partition_by(&[0]).unwrap();
+        assert_eq!(parts.len(), 3);
+        let a = &parts[&PartitionKey::from(vec![FxValue::from("a")])];
+        assert_eq!(a.schema().fields[0].name, "Col_0");
+        assert_eq!(
+            a.sequences()[1].to_values().unwrap(),
+            vec![FxValue::I32(1), FxValue::I32(3)]
+        );
+
+        let parts = batch.partition_by_names(["sym"]).unwrap();
+        let b = &parts[&PartitionKey::from(vec![FxValue::from("b")])];
+        assert_eq!(b.schema().fields[0].name, "sym");
+        assert_eq!(b.max_len(), Some(2));
+        let c = parts.get_by_key(&[FxValue::from("c")]).unwrap();
+        assert_eq!(c.max_len(), Some(1));
+        assert!(parts.get_by_key(&[FxValue::from("x")]).is_none());
+
+        assert!(batch.partition_by_names(["x"]).is_err());
+    }
+
+    #[test]
+    fn partitioned_push_concat_success() {
+        let batch = gen_batch();
+        let mut p = FxPartitioned::try_new(batch.schema().clone(), ["sym"]).unwrap();
+
+        p.push(batch.clone()).unwrap();
+        p.push(batch.data().clone()).unwrap();
+        assert_eq!(p.len(), 3);
+
+        let a = &p.partitions()[&PartitionKey::from(vec![FxValue::from("a")])];
+        assert_eq!(a.data.len(), 2);
+        assert_eq!(p.get_by_key(&[FxValue::from("b")]).unwrap().data.len(), 2);
+        assert!(p.get_by_key(&[FxValue::from("x")]).is_err());
+
+        // rows of other partitions are rejected
+        let key = PartitionKey::from(vec![FxValue::from("a")]);
+        assert!(p.insert(key, batch.clone()).is_err());
+
+        let res = p.concat().unwrap();
+        println!("{res:?}");
+        assert_eq!(res.max_len(), Some(10));
+        assert_eq!(
+            res.sequences()[1].to_values().unwrap()[..4],
+            [
+                FxValue::I32(1),
+                FxValue::I32(3),
+                FxValue::I32(1),
+                FxValue::I32(3)
+            ]
+        );
+
+        Receptacle::<true, _, FxBatch>::remove(&mut p, vec![FxValue::from("c")].into()).unwrap();
+        let batches = p.into_batches();
+        assert_eq!(batches.data.len(), 4);
+        assert_eq!(batches.schema().fields[2].name, "price");
+    }
+}
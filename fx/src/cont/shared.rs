@@ -0,0 +1,212 @@
+//! file: shared.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 14:02:16 Sunday
+//! brief: Shared Tabular
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use arrow2::datatypes::Schema;
+
+use super::FxTabular;
+use crate::ab::{Confined, Dqs, Eclectic, Receptacle};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// FxSharedTabular
+//
+// A producer/consumer friendly `FxTabular`. Writers append under a short write lock, while readers
+// take a snapshot, which is simply an `Arc` of the current `FxTabular`. Since `FxTabular` holds
+// `Deque<ArcArr>`, copying on write only clones the `Arc` pointers of the arrays, and the snapshot
+// held by a reader is never affected by the following appends.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct FxSharedTabular {
+    inner: Arc<RwLock<Arc<FxTabular>>>,
+}
+
+impl Default for FxSharedTabular {
+    fn default() -> Self {
+        Self::new(FxTabular::new_empty())
+    }
+}
+
+impl FxSharedTabular {
+    fn read_lock(&self) -> FxResult<RwLockReadGuard<'_, Arc<FxTabular>>> {
+        self.inner
+            .read()
+            .map_err(|e| FxError::InvalidOperation(e.to_string()))
+    }
+
+    fn write_lock(&self) -> FxResult<RwLockWriteGuard<'_, Arc<FxTabular>>> {
+        self.inner
+            .write()
+            .map_err(|e| FxError::InvalidOperation(e.to_string()))
+    }
+
+    pub fn new(data: FxTabular) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(data))),
+        }
+    }
+
+    pub fn try_empty_with_schema(schema: Schema) -> FxResult<Self> {
+        Ok(Self::new(FxTabular::try_empty_with_schema(schema)?))
+    }
+
+    /// Returns an immutable snapshot of the current data.
+    pub fn snapshot(&self) -> FxResult<Arc<FxTabular>> {
+        Ok(Arc::clone(&*self.read_lock()?))
+    }
+
+    pub fn schema(&self) -> FxResult<Schema> {
+        Ok(self.read_lock()?.schema().clone())
+    }
+
+    /// Returns the total length of arrays of the longest column.
+    pub fn rows(&self) -> FxResult<usize> {
+        Ok(self.read_lock()?.max_array_len().unwrap_or(0))
+    }
+
+    /// Appends an [`Eclectic`] to the back of every deque.
+    /// # Errors
+    /// This function will return an error if data types mismatch the schema.
+    pub fn push<E: Eclectic + Confined>(&self, data: E) -> FxResult<()> {
+        let mut guard = self.write_lock()?;
+
+        Receptacle::push(Arc::make_mut(&mut guard), data)
+    }
+
+    /// Appends many [`Eclectic`]s under a single write lock.
+    /// # Errors
+    /// This function will return an error once any of the data types mismatch the schema, and the
+    /// data before it are kept.
+    pub fn extend<I, E>(&self, data: I) -> FxResult<()>
+    where
+        I: IntoIterator<Item = E>,
+        E: Eclectic + Confined,
+    {
+        let mut guard = self.write_lock()?;
+        let tabular = Arc::make_mut(&mut guard);
+
+        Receptacle::extend(tabular, data)
+    }
+
+    /// Mutates the inner [`FxTabular`] under the write lock. Readers holding a snapshot are not
+    /// affected.
+    pub fn write<F, T>(&self, f: F) -> FxResult<T>
+    where
+        F: FnOnce(&mut FxTabular) -> FxResult<T>,
+    {
+        let mut guard = self.write_lock()?;
+
+        f(Arc::make_mut(&mut guard))
+    }
+
+    /// Takes the current data out, and leaves an empty [`FxTabular`] with the same schema.
+    pub fn take(&self) -> FxResult<FxTabular> {
+        let mut guard = self.write_lock()?;
+
+        let empty = Arc::new(FxTabular::try_empty_with_schema(guard.schema().clone())?);
+        let data = std::mem::replace(&mut *guard, empty);
+
+        Ok(Arc::try_unwrap(data).unwrap_or_else(|a| a.as_ref().clone()))
+    }
+}
+
+impl From<FxTabular> for FxSharedTabular {
+    fn from(data: FxTabular) -> Self {
+        Self::new(data)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_shared {
+    use super::*;
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+
+    fn new_shared() -> FxSharedTabular {
+        let d = FxTabular::new_with_names(vec![arc_arr!([0]), arc_arr!(["0"])], ["n", "s"]);
+
+        FxSharedTabular::new(d)
+    }
+
+    #[test]
+    fn snapshot_isolation_success() {
+        let shared = new_shared();
+
+        let snapshot = shared.snapshot().unwrap();
+        assert_eq!(snapshot.max_array_len(), Some(1));
+
+        shared
+            .push(vec![arc_arr!([1, 2]), arc_arr!(["1", "2"])])
+            .unwrap();
+
+        // the old snapshot is untouched
+        assert_eq!(snapshot.max_array_len(), Some(1));
+        assert_eq!(shared.rows().unwrap(), 3);
+
+        // schema mismatch
+        let res = shared.push(vec![arc_arr!(["x"]), arc_arr!([1])]);
+        assert!(res.is_err());
+        assert_eq!(shared.rows().unwrap(), 3);
+
+        let taken = shared.take().unwrap();
+        assert_eq!(taken.max_array_len(), Some(3));
+        assert_eq!(shared.rows().unwrap(), 0);
+        assert_eq!(shared.schema().unwrap().fields.len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_append_read_success() {
+        let shared = new_shared();
+        let batches = 200;
+
+        let writer = {
+            let shared = shared.clone();
+            tokio::spawn(async move {
+                for i in 1..=batches {
+                    let s = i.to_string();
+                    shared
+                        .push(vec![arc_arr!([i, i]), arc_arr!([s.as_str(), s.as_str()])])
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        let readers = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                tokio::spawn(async move {
+                    let mut last = 0;
+                    loop {
+                        let snapshot = shared.snapshot().unwrap();
+                        // every snapshot is consistent across columns
+                        assert!(snapshot.is_array_lens_equal());
+                        let rows = snapshot.max_array_len().unwrap();
+                        // and never goes backwards
+                        assert!(rows >= last);
+                        last = rows;
+                        if rows == 1 + 2 * batches as usize {
+                            break;
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        writer.await.unwrap();
+        for r in readers {
+            r.await.unwrap();
+        }
+
+        assert_eq!(shared.snapshot().unwrap().deque_lens(), vec![201, 201]);
+    }
+}
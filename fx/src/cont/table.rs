@@ -8,9 +8,10 @@ use std::ops::RangeBounds;
 use arrow2::datatypes::{DataType, Field, Schema};
 use inherent::inherent;
 
-use super::{ArcArr, DequeArcArr, DequeIterMut, DequeIterOwned, DequeIterRef};
+use super::{ArcArr, DequeArcArr, DequeIterMut, DequeIterOwned, DequeIterRef, FxTabular};
 use crate::ab::dqs::{Dqs, EclecticGetMut};
-use crate::ab::{private, Confined, Eclectic, FxSeq, Purport, StaticPurport};
+use crate::ab::evolve::{cast_deque, check_incoming, EvolvePlan};
+use crate::ab::{private, Confined, Eclectic, Evolve, FxSeq, Purport, SchemaChange, StaticPurport};
 use crate::error::{FxError, FxResult};
 
 // ================================================================================================
@@ -313,6 +314,59 @@ impl<const W: usize> Dqs for FxTable<W> {
     }
 }
 
+// ================================================================================================
+// Conversion
+// ================================================================================================
+
+impl<const W: usize> TryFrom<FxTabular> for FxTable<W> {
+    type Error = FxError;
+
+    fn try_from(tabular: FxTabular) -> FxResult<Self> {
+        let schema = tabular.schema().clone();
+        let data = tabular.take_data();
+
+        let len = data.len();
+        let data: [DequeArcArr; W] = data
+            .try_into()
+            .map_err(|_| FxError::LengthMismatch(len, W))?;
+
+        Ok(Self { schema, data })
+    }
+}
+
+// ================================================================================================
+// impl Evolve
+//
+// `FxTable` has a fixed width, hence new columns are rejected.
+// ================================================================================================
+
+impl<const W: usize, E: Eclectic> Evolve<E> for FxTable<W> {
+    fn push_evolving(&mut self, data: E, schema: &Schema) -> FxResult<Vec<SchemaChange>> {
+        check_incoming(&data, schema)?;
+
+        let plan = EvolvePlan::try_new(&self.schema, schema, false)?;
+
+        let data = plan.evolve_incoming_seqs(Vec::from(Self::_eclectic_into(data)?))?;
+        let mut data = Self::_eclectic_into(data)?;
+
+        // work on a copy (arrays are shared), hence nothing changes if any step fails
+        let mut evolved = self.data.clone();
+        for (idx, datatype) in plan.widened.iter() {
+            evolved[*idx] = cast_deque(&evolved[*idx], datatype)?;
+        }
+        for (idx, dq) in evolved.iter_mut().enumerate() {
+            let mut tmp = ArcArr::new_empty(DataType::Null);
+            std::mem::swap(&mut tmp, data.get_mut(idx).unwrap());
+            dq.push_back(tmp)?;
+        }
+
+        self.data = evolved;
+        self.schema = plan.schema;
+
+        Ok(plan.changes)
+    }
+}
+
 // ================================================================================================
 // impl Receptacle
 // ================================================================================================
@@ -449,4 +503,36 @@ mod test_table {
         println!("{:?}", c);
         println!("{:?}", d.ref_data());
     }
+
+    #[test]
+    fn push_evolving_success() {
+        let mut d = FxTable::<2>::new_with_names(
+            vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])],
+            ["c1", "c2"],
+        );
+
+        let schema = Schema::from(vec![
+            Field::new("c2", DataType::Utf8, false),
+            Field::new("c1", DataType::Int64, false),
+        ]);
+        let changes = d
+            .push_evolving([arc_arr!(["c"]), arc_arr!([3i64])], &schema)
+            .unwrap();
+        println!("{:?}", changes);
+
+        assert_eq!(d.data_types(), vec![&DataType::Int64, &DataType::Utf8]);
+        assert_eq!(d.array_lens(), vec![3, 3]);
+
+        // new columns are rejected
+        let schema = Schema::from(vec![
+            Field::new("c1", DataType::Int64, false),
+            Field::new("c2", DataType::Utf8, false),
+            Field::new("c3", DataType::Utf8, false),
+        ]);
+        let res = d.push_evolving(
+            vec![arc_arr!([4i64]), arc_arr!(["d"]), arc_arr!(["x"])],
+            &schema,
+        );
+        assert!(res.is_err());
+    }
 }
@@ -8,9 +8,10 @@ use std::ops::RangeBounds;
 use arrow2::datatypes::{DataType, Field, Schema};
 use inherent::inherent;
 
-use super::{ArcArr, DequeArcArr, DequeIterMut, DequeIterOwned, DequeIterRef};
+use super::{ArcArr, DequeArcArr, DequeIterMut, DequeIterOwned, DequeIterRef, FxTable};
 use crate::ab::dqs::{Dqs, EclecticGetMut};
-use crate::ab::{private, Confined, Eclectic, FxSeq, Purport, StaticPurport};
+use crate::ab::evolve::{cast_deque, check_incoming, EvolvePlan};
+use crate::ab::{private, Confined, Eclectic, Evolve, FxSeq, Purport, SchemaChange, StaticPurport};
 use crate::error::{FxError, FxResult};
 
 // ================================================================================================
@@ -342,6 +343,157 @@ impl Dqs for FxTabular {
     }
 }
 
+// ================================================================================================
+// Column methods
+//
+// Unlike `FxTable`, the width of `FxTabular` is not fixed, hence columns can be added, removed,
+// renamed or casted, while `Purport::schema` is kept in sync.
+// ================================================================================================
+
+impl FxTabular {
+    fn column_position(&self, name: &str) -> FxResult<usize> {
+        self.schema
+            .fields
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| FxError::InvalidArgument(format!("column `{name}` not found")))
+    }
+
+    /// Appends a new column at the end of this [`FxTabular`].
+    /// # Errors
+    /// This function will return an error if the name already exists, the datatype mismatches
+    /// the field, or the total length of arrays differs from the longest existing column.
+    pub fn add_column(&mut self, field: Field, data: DequeArcArr) -> FxResult<()> {
+        if self.column_position(&field.name).is_ok() {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{}` already exists",
+                field.name
+            )));
+        }
+
+        let data = match data.datatype() {
+            Some(d) if d != field.data_type() => return Err(FxError::DatatypeMismatch),
+            Some(_) => data,
+            None => DequeArcArr::new_empty_with_type(field.data_type().clone()),
+        };
+
+        if let Some(rows) = self.max_array_len() {
+            if data.array_len() != rows {
+                return Err(FxError::LengthMismatch(data.array_len(), rows));
+            }
+        }
+
+        self.schema.fields.push(field);
+        self.data.push(data);
+
+        Ok(())
+    }
+
+    /// Removes a column by name, returns its field and data.
+    pub fn remove_column(&mut self, name: &str) -> FxResult<(Field, DequeArcArr)> {
+        let idx = self.column_position(name)?;
+
+        let field = self.schema.fields.remove(idx);
+        let data = self.data.remove(idx);
+
+        Ok((field, data))
+    }
+
+    /// Renames a column.
+    /// # Errors
+    /// This function will return an error if `name` is not found or `new_name` already exists.
+    pub fn rename_column(&mut self, name: &str, new_name: &str) -> FxResult<()> {
+        let idx = self.column_position(name)?;
+
+        if name != new_name && self.column_position(new_name).is_ok() {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{new_name}` already exists"
+            )));
+        }
+
+        self.schema.fields[idx].name = new_name.to_string();
+
+        Ok(())
+    }
+
+    /// Casts every array of a column into `datatype`, and updates the field's datatype.
+    pub fn cast_column(&mut self, name: &str, datatype: DataType) -> FxResult<()> {
+        let idx = self.column_position(name)?;
+
+        self.data[idx] = self.data[idx].try_cast(&datatype)?;
+        self.schema.fields[idx].data_type = datatype;
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// impl Evolve
+// ================================================================================================
+
+impl<E: Eclectic> Evolve<E> for FxTabular {
+    fn push_evolving(&mut self, data: E, schema: &Schema) -> FxResult<Vec<SchemaChange>> {
+        check_incoming(&data, schema)?;
+
+        // an empty tabular simply adopts the incoming schema
+        if self.schema.fields.is_empty() {
+            self.data = from_vecaa(Self::_eclectic_into(data)?);
+            self.schema = schema.clone();
+            return Ok(vec![]);
+        }
+
+        let plan = EvolvePlan::try_new(&self.schema, schema, true)?;
+
+        let data = plan.evolve_incoming_seqs(Self::_eclectic_into(data)?)?;
+
+        // work on a copy (arrays are shared), hence nothing changes if any step fails
+        let mut evolved = self.clone();
+        for (idx, datatype) in plan.widened.iter() {
+            evolved.data[*idx] = cast_deque(&evolved.data[*idx], datatype)?;
+        }
+        // back-fill new columns by nulls, whose arrays have the same lengths as the longest column
+        let lens = evolved
+            .data
+            .iter()
+            .max_by_key(|dq| dq.array_len())
+            .map(|dq| dq.len_of_arrays())
+            .unwrap_or_default();
+        let mut added = Vec::new();
+        for f in plan.added.iter() {
+            let nulls = lens
+                .iter()
+                .map(|l| ArcArr::new_nulls(f.data_type().clone(), *l))
+                .collect::<Vec<_>>();
+            let dq = if nulls.is_empty() {
+                DequeArcArr::new_empty_with_type(f.data_type().clone())
+            } else {
+                DequeArcArr::try_new(nulls)?
+            };
+            added.push(dq);
+        }
+        evolved.data.extend(added);
+        evolved.schema = plan.schema;
+
+        evolved.deque_push_back(data)?;
+        *self = evolved;
+
+        Ok(plan.changes)
+    }
+}
+
+// ================================================================================================
+// Conversion
+// ================================================================================================
+
+impl<const W: usize> From<FxTable<W>> for FxTabular {
+    fn from(table: FxTable<W>) -> Self {
+        let schema = table.schema().clone();
+        let data = Vec::from(table.take_data());
+
+        Self { schema, data }
+    }
+}
+
 // ================================================================================================
 // impl Receptacle
 // ================================================================================================
@@ -394,7 +546,7 @@ where
 #[cfg(test)]
 mod test_tabular {
     use super::*;
-    use crate::ab::FromSlice;
+    use crate::ab::{FromSlice, Receptacle};
     use crate::arc_arr;
 
     #[test]
@@ -478,4 +630,117 @@ mod test_tabular {
         println!("{:?}", c);
         println!("{:?}", d.ref_data());
     }
+
+    #[test]
+    fn column_add_remove_success() {
+        let mut d = FxTabular::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+
+        let dq = DequeArcArr::new(vec![arc_arr!([true]), arc_arr!([false, true])]);
+        let res = d.add_column(Field::new("c3", DataType::Boolean, false), dq);
+        assert!(res.is_ok());
+        assert_eq!(d.names(), vec!["c1", "c2", "c3"]);
+        assert_eq!(d.width(), 3);
+
+        // row counts mismatch
+        let dq = DequeArcArr::new(vec![arc_arr!([1.0, 2.0])]);
+        let res = d.add_column(Field::new("c4", DataType::Float64, false), dq);
+        assert!(res.is_err());
+
+        // duplicated name
+        let dq = DequeArcArr::new(vec![arc_arr!([1.0, 2.0, 3.0])]);
+        let res = d.add_column(Field::new("c1", DataType::Float64, false), dq);
+        assert!(res.is_err());
+
+        let (f, dq) = d.remove_column("c2").unwrap();
+        assert_eq!(f.name, "c2");
+        assert_eq!(dq.array_len(), 3);
+        assert_eq!(d.names(), vec!["c1", "c3"]);
+        assert_eq!(d.ref_data().len(), 2);
+
+        assert!(d.remove_column("c2").is_err());
+    }
+
+    #[test]
+    fn column_rename_cast_success() {
+        let mut d = FxTabular::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        d.push_back(vec![arc_arr!([4]), arc_arr!(["d"])]).unwrap();
+
+        assert!(d.rename_column("c1", "c2").is_err());
+        assert!(d.rename_column("c1", "x").is_ok());
+        assert_eq!(d.names(), vec!["x", "c2"]);
+
+        assert!(d.cast_column("x", DataType::Float64).is_ok());
+        assert_eq!(d.data_types()[0], &DataType::Float64);
+        assert!(d.ref_data()[0].data_type_match(&DataType::Float64));
+        assert_eq!(d.ref_data()[0].len(), 2);
+
+        println!("{:?}", d);
+    }
+
+    #[test]
+    fn table_tabular_conversion_success() {
+        let t = FxTable::<2>::new(vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])]);
+
+        let d = FxTabular::from(t);
+        assert_eq!(d.width(), 2);
+
+        let t = FxTable::<2>::try_from(d.clone());
+        assert!(t.is_ok());
+
+        let t = FxTable::<3>::try_from(d);
+        assert!(t.is_err());
+    }
+
+    #[test]
+    fn push_evolving_success() {
+        let mut d = FxTabular::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+
+        // reordered, widened and a new column
+        let data = vec![
+            arc_arr!(["d", "e"]),
+            arc_arr!([true, false]),
+            arc_arr!([4.0, 5.0]),
+        ];
+        let schema = Schema::from(vec![
+            Field::new("c2", DataType::Utf8, false),
+            Field::new("c3", DataType::Boolean, false),
+            Field::new("c1", DataType::Float64, false),
+        ]);
+
+        let changes = d.push_evolving(data, &schema).unwrap();
+        println!("{:?}", changes);
+        assert_eq!(changes.len(), 2);
+
+        assert_eq!(d.names(), vec!["c1", "c2", "c3"]);
+        assert_eq!(
+            d.data_types(),
+            vec![&DataType::Float64, &DataType::Utf8, &DataType::Boolean]
+        );
+        assert!(d.schema().fields[2].is_nullable);
+        assert_eq!(d.deque_lens(), vec![2, 2, 2]);
+        assert_eq!(d.array_lens(), vec![5, 5, 5]);
+
+        // the plain `push` works with the evolved schema
+        let data = vec![arc_arr!([6.0]), arc_arr!(["f"]), arc_arr!([true])];
+        assert!(d.push(data).is_ok());
+
+        // unknown type
+        let schema = Schema::from(vec![
+            Field::new("c1", DataType::Utf8, false),
+            Field::new("c2", DataType::Utf8, false),
+            Field::new("c3", DataType::Boolean, false),
+        ]);
+        let data = vec![arc_arr!(["x"]), arc_arr!(["f"]), arc_arr!([true])];
+        assert!(d.push_evolving(data, &schema).is_err());
+        assert_eq!(d.array_lens(), vec![6, 6, 6]);
+    }
 }
@@ -65,6 +65,9 @@ pub enum FxError {
 
     #[error("{0}")]
     ArrowAvro(arrow2::io::avro::avro_schema::error::Error),
+
+    #[error("{0}")]
+    Calamine(calamine::Error),
 }
 
 impl From<std::io::Error> for FxError {
@@ -90,3 +93,9 @@ impl From<arrow2::io::avro::avro_schema::error::Error> for FxError {
         FxError::ArrowAvro(e)
     }
 }
+
+impl From<calamine::Error> for FxError {
+    fn from(e: calamine::Error) -> Self {
+        FxError::Calamine(e)
+    }
+}
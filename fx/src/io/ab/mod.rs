@@ -0,0 +1,398 @@
+//! file: mod.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 20:31:06 Sunday
+//! brief: I/O abstraction
+
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::compute::concatenate::concatenate;
+use arrow2::datatypes::Schema;
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use crate::ab::{Confined, Dqs, Eclectic, FxSeq, Receptacle};
+use crate::cont::{ChunkArr, FxBatches, FxBundles, FxTable, FxTabular};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// FromChunks
+//
+// Receptacles which can be built from all the chunks of a file
+// (IPC record batches, Parquet row groups, Avro blocks).
+// ================================================================================================
+
+pub trait FromChunks<E: Eclectic>: Sized {
+    fn try_from_chunks<I>(schema: Schema, chunks: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = FxResult<E>>;
+}
+
+impl<E: Eclectic + Confined> FromChunks<E> for Vec<E> {
+    fn try_from_chunks<I>(_schema: Schema, chunks: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = FxResult<E>>,
+    {
+        chunks.into_iter().collect()
+    }
+}
+
+impl<E: Eclectic + Confined> FromChunks<E> for FxBatches<E> {
+    fn try_from_chunks<I>(schema: Schema, chunks: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = FxResult<E>>,
+    {
+        let mut res = FxBatches::empty_with_schema(schema);
+        for c in chunks {
+            res.push(c?)?;
+        }
+
+        Ok(res)
+    }
+}
+
+impl<E: Eclectic + Confined> FromChunks<E> for FxTabular {
+    fn try_from_chunks<I>(schema: Schema, chunks: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = FxResult<E>>,
+    {
+        let mut res = FxTabular::try_empty_with_schema(schema)?;
+        for c in chunks {
+            res.push(c?)?;
+        }
+
+        Ok(res)
+    }
+}
+
+impl<const W: usize, E: Eclectic + Confined> FromChunks<E> for FxTable<W> {
+    fn try_from_chunks<I>(schema: Schema, chunks: I) -> FxResult<Self>
+    where
+        I: IntoIterator<Item = FxResult<E>>,
+    {
+        let mut res = FxTable::<W>::try_empty_with_schema(schema)?;
+        for c in chunks {
+            res.push(c?)?;
+        }
+
+        Ok(res)
+    }
+}
+
+// ================================================================================================
+// IntoChunks
+//
+// Receptacles whose chunks can be written one by one
+// (IPC record batches, Parquet row groups, Avro blocks), without concatenating them in memory.
+// ================================================================================================
+
+pub trait IntoChunks {
+    fn into_chunks(self) -> FxResult<(Schema, Vec<ChunkArr>)>;
+}
+
+fn eclectic_to_chunk<E: Eclectic>(data: E) -> FxResult<ChunkArr> {
+    let arrays = data
+        .take_sequences()
+        .into_iter()
+        .map(FxSeq::to_arc_array)
+        .collect::<FxResult<Vec<_>>>()?;
+
+    Ok(Chunk::try_new(arrays)?)
+}
+
+impl<E: Eclectic> IntoChunks for FxBatches<E> {
+    fn into_chunks(self) -> FxResult<(Schema, Vec<ChunkArr>)> {
+        let chunks = self
+            .data
+            .into_iter()
+            .map(eclectic_to_chunk)
+            .collect::<FxResult<Vec<_>>>()?;
+
+        Ok((self.schema, chunks))
+    }
+}
+
+impl<const W: usize, S: FxSeq> IntoChunks for FxBundles<W, S> {
+    fn into_chunks(self) -> FxResult<(Schema, Vec<ChunkArr>)> {
+        let chunks = self
+            .data
+            .into_iter()
+            .map(eclectic_to_chunk)
+            .collect::<FxResult<Vec<_>>>()?;
+
+        Ok((self.schema, chunks))
+    }
+}
+
+// arrays of the other deques are re-sized by the first deque's
+macro_rules! impl_into_chunks_for_dqs {
+    () => {
+        fn into_chunks(mut self) -> FxResult<(Schema, Vec<ChunkArr>)> {
+            if !self.is_array_lens_equal() {
+                return Err(FxError::LengthMismatch(
+                    self.min_array_len().unwrap_or(0),
+                    self.max_array_len().unwrap_or(0),
+                ));
+            }
+
+            let sequence = self
+                .ref_data()
+                .first()
+                .map(|dq| dq.len_of_arrays())
+                .unwrap_or_default();
+            let chunks = self.to_chunks_by_sequence(&sequence);
+
+            Ok((self.schema().clone(), chunks))
+        }
+    };
+}
+
+impl IntoChunks for FxTabular {
+    impl_into_chunks_for_dqs!();
+}
+
+impl<const W: usize> IntoChunks for FxTable<W> {
+    impl_into_chunks_for_dqs!();
+}
+
+/// Turns an `Eclectic` into chunks of at most `max_rows` rows, or a single chunk if `None`.
+pub(crate) fn eclectic_to_sized_chunks<E: Eclectic>(
+    data: E,
+    max_rows: Option<usize>,
+) -> FxResult<Vec<Chunk<Box<dyn Array>>>> {
+    let arrays = data
+        .take_sequences()
+        .into_iter()
+        .map(FxSeq::to_box_array)
+        .collect::<FxResult<Vec<_>>>()?;
+    let chunk = Chunk::try_new(arrays)?;
+
+    chunk_to_sized_chunks(chunk, max_rows)
+}
+
+/// Splits a chunk into chunks of at most `max_rows` rows.
+pub(crate) fn chunk_to_sized_chunks(
+    chunk: Chunk<Box<dyn Array>>,
+    max_rows: Option<usize>,
+) -> FxResult<Vec<Chunk<Box<dyn Array>>>> {
+    let max_rows = match max_rows {
+        Some(0) => {
+            return Err(FxError::InvalidArgument(
+                "max rows should be greater than 0".to_string(),
+            ))
+        }
+        Some(m) if m < chunk.len() => m,
+        _ => return Ok(vec![chunk]),
+    };
+
+    (0..chunk.len())
+        .step_by(max_rows)
+        .map(|offset| {
+            let len = max_rows.min(chunk.len() - offset);
+            let arrays = chunk.iter().map(|a| a.sliced(offset, len)).collect();
+            Ok(Chunk::try_new(arrays)?)
+        })
+        .collect()
+}
+
+// ================================================================================================
+// Schema validation
+// ================================================================================================
+
+/// Checks a file's schema against an expected one: field names and data types must be the same,
+/// and a nullable field cannot be read as a non-nullable one.
+pub fn check_schema(found: &Schema, expected: &Schema) -> FxResult<()> {
+    if found.fields.len() != expected.fields.len() {
+        return Err(FxError::LengthMismatch(
+            found.fields.len(),
+            expected.fields.len(),
+        ));
+    }
+
+    let matched = found
+        .fields
+        .iter()
+        .zip(expected.fields.iter())
+        .all(|(f, e)| {
+            f.name == e.name && f.data_type() == e.data_type() && (e.is_nullable || !f.is_nullable)
+        });
+    if !matched {
+        return Err(FxError::SchemaMismatch);
+    }
+
+    Ok(())
+}
+
+// ================================================================================================
+// FxChunkIter
+//
+// A streaming iterator over arrow2's chunk readers, each chunk is turned into an `Eclectic`.
+// Containers carrying a schema (e.g. `FxBatch`) inherit the file's schema.
+// Readers may fail by either arrow2's error or `FxError`, both are surfaced as `FxError`.
+// ================================================================================================
+
+pub struct FxChunkIter<I, D> {
+    schema: Schema,
+    chunks: I,
+    _p: PhantomData<D>,
+}
+
+impl<I, D, E> FxChunkIter<I, D>
+where
+    I: Iterator<Item = Result<Chunk<Box<dyn Array>>, E>>,
+    D: Eclectic,
+    FxError: From<E>,
+{
+    pub(crate) fn new(schema: Schema, chunks: I) -> Self {
+        Self {
+            schema,
+            chunks,
+            _p: PhantomData,
+        }
+    }
+
+    /// Schema of the underlying file.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Validates the file's schema against `expected`, see [`check_schema`].
+    pub fn expect_schema(self, expected: &Schema) -> FxResult<Self> {
+        check_schema(&self.schema, expected)?;
+
+        Ok(self)
+    }
+
+    /// Collects all the chunks into a receptacle.
+    pub fn collect_into<C: FromChunks<D>>(self) -> FxResult<C> {
+        let schema = self.schema.clone();
+
+        C::try_from_chunks(schema, self)
+    }
+
+    /// Concatenates all the chunks into one `Eclectic`.
+    /// # Errors
+    /// This function will return an error if there is no chunk at all.
+    pub fn concat(mut self) -> FxResult<D> {
+        let chunks = self.chunks.by_ref().collect::<Result<Vec<_>, _>>()?;
+
+        concat_chunks(&self.schema, chunks)
+    }
+}
+
+impl<I, D, E> Iterator for FxChunkIter<I, D>
+where
+    I: Iterator<Item = Result<Chunk<Box<dyn Array>>, E>>,
+    D: Eclectic,
+    FxError: From<E>,
+{
+    type Item = FxResult<D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+
+        Some(
+            chunk
+                .map_err(Into::into)
+                .and_then(|c| chunk_to_eclectic(&self.schema, c.into_arrays())),
+        )
+    }
+}
+
+fn chunk_to_eclectic<D: Eclectic>(schema: &Schema, arrays: Vec<Box<dyn Array>>) -> FxResult<D> {
+    let mut res = D::from_vec_seq(arrays.into_iter().map(FxSeq::from_box_arr).collect())?;
+    res.set_schema_unchecked(schema.clone());
+
+    Ok(res)
+}
+
+fn concat_chunks<D: Eclectic>(schema: &Schema, chunks: Vec<Chunk<Box<dyn Array>>>) -> FxResult<D> {
+    let arrays = match chunks.len() {
+        0 => return Err(FxError::EmptyContent),
+        1 => chunks.into_iter().next().unwrap().into_arrays(),
+        _ => (0..schema.fields.len())
+            .map(|col| {
+                let arrs = chunks.iter().map(|c| c[col].as_ref()).collect::<Vec<_>>();
+                concatenate(&arrs)
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    chunk_to_eclectic(schema, arrays)
+}
+
+// ================================================================================================
+// FxChunkStream
+//
+// The async counterpart of `FxChunkIter`, over arrow2's async chunk readers.
+// ================================================================================================
+
+pub struct FxChunkStream<S, D> {
+    schema: Schema,
+    chunks: S,
+    _p: PhantomData<fn() -> D>,
+}
+
+impl<S, D> FxChunkStream<S, D>
+where
+    S: Stream<Item = arrow2::error::Result<Chunk<Box<dyn Array>>>> + Unpin,
+    D: Eclectic,
+{
+    pub(crate) fn new(schema: Schema, chunks: S) -> Self {
+        Self {
+            schema,
+            chunks,
+            _p: PhantomData,
+        }
+    }
+
+    /// Schema of the underlying file.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Validates the file's schema against `expected`, see [`check_schema`].
+    pub fn expect_schema(self, expected: &Schema) -> FxResult<Self> {
+        check_schema(&self.schema, expected)?;
+
+        Ok(self)
+    }
+
+    /// Collects all the chunks into a receptacle.
+    pub async fn collect_into<C: FromChunks<D>>(self) -> FxResult<C> {
+        let schema = self.schema.clone();
+        let chunks = self.collect::<Vec<_>>().await;
+
+        C::try_from_chunks(schema, chunks)
+    }
+
+    /// Concatenates all the chunks into one `Eclectic`.
+    /// # Errors
+    /// This function will return an error if there is no chunk at all.
+    pub async fn concat(self) -> FxResult<D> {
+        let chunks = self.chunks.try_collect::<Vec<_>>().await?;
+
+        concat_chunks(&self.schema, chunks)
+    }
+}
+
+impl<S, D> Stream for FxChunkStream<S, D>
+where
+    S: Stream<Item = arrow2::error::Result<Chunk<Box<dyn Array>>>> + Unpin,
+    D: Eclectic,
+{
+    type Item = FxResult<D>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        this.chunks.poll_next_unpin(cx).map(|chunk| {
+            chunk.map(|c| {
+                c.map_err(Into::into)
+                    .and_then(|c| chunk_to_eclectic(&this.schema, c.into_arrays()))
+            })
+        })
+    }
+}
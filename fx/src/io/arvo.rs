@@ -5,67 +5,526 @@
 
 use std::io::Write;
 
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::compute::cast::CastOptions;
+use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::io::avro::avro_schema;
 use arrow2::io::avro::read as avro_read;
 use arrow2::io::avro::write as avro_write;
+use futures::stream::BoxStream;
+use futures::{AsyncRead, AsyncWrite, AsyncWriteExt, Stream, StreamExt};
 
 use super::{ec::ReadSeek, FxIO, SimpleIO};
-use crate::ab::{Congruent, Eclectic, FxSeq, Purport};
+use crate::ab::{Congruent, Eclectic, FromChunks, FxChunkIter, FxChunkStream, IntoChunks, Purport};
 use crate::error::{FxError, FxResult};
+use crate::io::ab::{chunk_to_sized_chunks, eclectic_to_sized_chunks};
 
 // ================================================================================================
 // Arvo
 // ================================================================================================
 
 impl FxIO {
+    /// Writes blocks of at most `DEFAULT_AVRO_BLOCK_SIZE` rows.
     pub fn write_avro<D: Eclectic + Purport, W: Write>(
         data: D,
-        mut writer: W,
+        writer: W,
         options: Option<avro_schema::file::Compression>,
     ) -> FxResult<()> {
-        let record = avro_write::to_record(data.schema())?;
-        let arrays = data.take_shortest_to_chunk()?;
+        let options = AvroWriteOptions::default().with_compression(options);
 
-        let mut serializers = arrays
-            .iter()
-            .zip(record.fields.iter())
-            .map(|(array, field)| avro_write::new_serializer(array.as_ref(), &field.schema))
-            .collect::<Vec<_>>();
-        let mut block = avro_schema::file::Block::new(arrays[0].len(), vec![]);
+        Self::write_avro_with(data, writer, options)
+    }
+
+    /// Writes blocks of at most `block_size` rows, or a single block if `None`.
+    pub fn write_avro_with<D: Eclectic + Purport, W: Write>(
+        data: D,
+        mut writer: W,
+        options: AvroWriteOptions,
+    ) -> FxResult<()> {
+        let record = schema_to_record(data.schema())?;
+        let chunk = data.take_shortest_to_chunk()?;
+        let chunk = Chunk::try_new(chunk.iter().map(|a| a.to_boxed()).collect())?;
 
-        avro_write::serialize(&mut serializers, &mut block);
+        avro_schema::write::write_metadata(&mut writer, record.clone(), options.compression)?;
 
-        let mut compressed_block = avro_schema::file::CompressedBlock::default();
+        for chunk in chunk_to_sized_chunks(chunk, options.block_size)? {
+            let compressed_block = chunk_to_block(&chunk, &record, options.compression)?;
 
-        let _was_compressed =
-            avro_schema::write::compress(&mut block, &mut compressed_block, options)?;
+            avro_schema::write::write_block(&mut writer, &compressed_block)?;
+        }
 
-        avro_schema::write::write_metadata(&mut writer, record, options)?;
+        Ok(())
+    }
 
-        avro_schema::write::write_block(&mut writer, &compressed_block)?;
+    /// Writes one block per chunk, chunks longer than `max_rows` are split.
+    pub fn write_avro_iter<E, I, W>(
+        schema: Schema,
+        data: I,
+        mut writer: W,
+        max_rows: Option<usize>,
+        options: Option<avro_schema::file::Compression>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let record = schema_to_record(&schema)?;
+
+        avro_schema::write::write_metadata(&mut writer, record.clone(), options)?;
+
+        for d in data {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                let compressed_block = chunk_to_block(&chunk, &record, options)?;
+
+                avro_schema::write::write_block(&mut writer, &compressed_block)?;
+            }
+        }
 
         Ok(())
     }
 
+    /// Writes one block per chunk of a receptacle, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn write_avro_from<C: IntoChunks, W: Write>(
+        data: C,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<avro_schema::file::Compression>,
+    ) -> FxResult<()> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_avro_iter(schema, chunks, writer, max_rows, options)
+    }
+
+    /// Reads all blocks lazily.
+    pub fn read_avro_iter<D: Eclectic, R: ReadSeek>(
+        reader: R,
+    ) -> FxResult<FxChunkIter<AvroChunks<R>, D>> {
+        Self::read_avro_iter_with(reader, AvroReadOptions::default())
+    }
+
+    /// Reads all blocks lazily, resolved against the reader schema if any: fields are matched by
+    /// name, returned in the order of the reader schema, and promoted to its types when they differ.
+    pub fn read_avro_iter_with<D: Eclectic, R: ReadSeek>(
+        mut reader: R,
+        options: AvroReadOptions,
+    ) -> FxResult<FxChunkIter<AvroChunks<R>, D>> {
+        let metadata = avro_schema::read::read_metadata(&mut reader)?;
+
+        let schema = infer_schema(&metadata.record)?;
+
+        let Some(reader_schema) = options.reader_schema else {
+            let blocks = avro_read::Reader::new(reader, metadata, schema.fields.clone(), None);
+            let chunks = AvroChunks {
+                reader: blocks,
+                columns: None,
+            };
+
+            let iter = FxChunkIter::new(schema, chunks);
+            return match &options.expected_schema {
+                Some(expected) => iter.expect_schema(expected),
+                None => Ok(iter),
+            };
+        };
+
+        if reader_schema.fields.is_empty() {
+            return Err(FxError::InvalidArgument(
+                "reader schema should not be empty".to_string(),
+            ));
+        }
+
+        let mut projection = vec![false; schema.fields.len()];
+        let mut resolved = Vec::with_capacity(reader_schema.fields.len());
+        for field in reader_schema.fields.iter() {
+            let (idx, writer_field) = schema
+                .fields
+                .iter()
+                .enumerate()
+                .find(|(_, f)| f.name == field.name)
+                .ok_or_else(|| {
+                    FxError::InvalidArgument(format!("column `{}` not found", field.name))
+                })?;
+
+            let cast = if writer_field.data_type == field.data_type {
+                None
+            } else if can_promote(&writer_field.data_type, &field.data_type) {
+                Some(field.data_type.clone())
+            } else {
+                return Err(FxError::InvalidType(format!(
+                    "column `{}` of type {:?} cannot be read as {:?}",
+                    field.name, writer_field.data_type, field.data_type
+                )));
+            };
+
+            projection[idx] = true;
+            resolved.push((idx, cast));
+        }
+
+        // projected arrays come in the order of the writer schema
+        let columns = resolved
+            .into_iter()
+            .map(|(idx, cast)| (projection[..idx].iter().filter(|p| **p).count(), cast))
+            .collect();
+
+        let blocks = avro_read::Reader::new(reader, metadata, schema.fields, Some(projection));
+        let chunks = AvroChunks {
+            reader: blocks,
+            columns: Some(columns),
+        };
+
+        let iter = FxChunkIter::new(reader_schema, chunks);
+        match &options.expected_schema {
+            Some(expected) => iter.expect_schema(expected),
+            None => Ok(iter),
+        }
+    }
+
+    /// Reads all blocks into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` & `FxTabular`.
+    pub fn read_avro_into<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(reader: R) -> FxResult<C> {
+        Self::read_avro_iter::<D, R>(reader)?.collect_into()
+    }
+
+    /// Reads all blocks into a receptacle, see `read_avro_iter_with`.
+    pub fn read_avro_into_with<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(
+        reader: R,
+        options: AvroReadOptions,
+    ) -> FxResult<C> {
+        Self::read_avro_iter_with::<D, R>(reader, options)?.collect_into()
+    }
+
+    /// Reads all blocks and concatenates them into one `Eclectic`.
     pub fn read_avro<D: Eclectic + Purport, R: ReadSeek>(reader: &mut R) -> FxResult<D> {
-        let metadata = avro_schema::read::read_metadata(reader)?;
+        Self::read_avro_iter::<D, _>(reader)?.concat()
+    }
 
-        let schema = avro_read::infer_schema(&metadata.record)?;
+    /// Reads all blocks into one `Eclectic`, see `read_avro_iter_with`.
+    pub fn read_avro_with<D: Eclectic + Purport, R: ReadSeek>(
+        reader: &mut R,
+        options: AvroReadOptions,
+    ) -> FxResult<D> {
+        Self::read_avro_iter_with::<D, _>(reader, options)?.concat()
+    }
+}
 
-        let mut blocks = avro_read::Reader::new(reader, metadata, schema.fields, None);
+// Avro schema resolution: int is promotable to long, float & double, long to float & double, float
+// to double, and string & bytes to each other; timestamps of other timezones are also accepted
+fn can_promote(from: &DataType, to: &DataType) -> bool {
+    use DataType::*;
+
+    match (from, to) {
+        (Timestamp(f, _), Timestamp(t, _)) => f == t,
+        _ => matches!(
+            (from, to),
+            (Int32, Int64 | Float32 | Float64)
+                | (Int64, Float32 | Float64)
+                | (Float32, Float64)
+                | (Utf8, Binary)
+                | (Binary, Utf8)
+        ),
+    }
+}
 
-        let mut res = None;
-        if let Some(Ok(c)) = blocks.next() {
-            let data = c
-                .into_arrays()
-                .into_iter()
-                .map(FxSeq::from_box_arr)
-                .collect::<Vec<_>>();
+// `timestamp-*` are UTC-adjusted instants, which arrow2 reads with the timezone `00:00`. They are
+// read as `+00:00` instead, so that UTC timestamps round-trip; other timezones are not kept by
+// Avro, and their instants are read back in UTC as well
+fn infer_schema(record: &avro_schema::schema::Record) -> FxResult<Schema> {
+    let mut schema = avro_read::infer_schema(record)?;
+    for field in schema.fields.iter_mut() {
+        if let DataType::Timestamp(_, tz @ Some(_)) = &mut field.data_type {
+            *tz = Some("+00:00".to_string());
+        }
+    }
+
+    Ok(schema)
+}
+
+// arrow2 maps timestamps without a timezone to `local-timestamp-*`, but refuses the ones with a
+// timezone, which are UTC-adjusted instants, i.e. `timestamp-millis` & `timestamp-micros`
+fn schema_to_record(schema: &Schema) -> FxResult<avro_schema::schema::Record> {
+    use avro_schema::schema::{LongLogical, Schema as AvroSchema};
+
+    let local = schema
+        .fields
+        .iter()
+        .map(|f| match &f.data_type {
+            DataType::Timestamp(unit, Some(_)) => Field {
+                data_type: DataType::Timestamp(*unit, None),
+                ..f.clone()
+            },
+            _ => f.clone(),
+        })
+        .collect::<Vec<_>>();
+    let mut record = avro_write::to_record(&Schema::from(local))?;
+
+    let to_utc = |s: &mut AvroSchema| match s {
+        AvroSchema::Long(l @ Some(LongLogical::LocalTimestampMillis)) => {
+            *l = Some(LongLogical::TimestampMillis)
+        }
+        AvroSchema::Long(l @ Some(LongLogical::LocalTimestampMicros)) => {
+            *l = Some(LongLogical::TimestampMicros)
+        }
+        _ => {}
+    };
+    for (field, avro_field) in schema.fields.iter().zip(record.fields.iter_mut()) {
+        if let DataType::Timestamp(_, Some(_)) = field.data_type {
+            match &mut avro_field.schema {
+                AvroSchema::Union(schemas) => schemas.iter_mut().for_each(to_utc),
+                s => to_utc(s),
+            }
+        }
+    }
+
+    Ok(record)
+}
+
+fn chunk_to_block(
+    chunk: &Chunk<Box<dyn Array>>,
+    record: &avro_schema::schema::Record,
+    options: Option<avro_schema::file::Compression>,
+) -> FxResult<avro_schema::file::CompressedBlock> {
+    let mut serializers = chunk
+        .iter()
+        .zip(record.fields.iter())
+        .map(|(array, field)| avro_write::new_serializer(array.as_ref(), &field.schema))
+        .collect::<Vec<_>>();
+    let mut block = avro_schema::file::Block::new(chunk.len(), vec![]);
+
+    avro_write::serialize(&mut serializers, &mut block);
+
+    let mut compressed_block = avro_schema::file::CompressedBlock::default();
+
+    let _was_compressed = avro_schema::write::compress(&mut block, &mut compressed_block, options)?;
+
+    Ok(compressed_block)
+}
+
+// ================================================================================================
+// AvroChunks
+// ================================================================================================
+
+pub struct AvroChunks<R: ReadSeek> {
+    reader: avro_read::Reader<R>,
+    // position in the projected chunk & type to cast to, per field of the reader schema
+    columns: Option<Vec<(usize, Option<DataType>)>>,
+}
+
+impl<R: ReadSeek> AvroChunks<R> {
+    fn resolve(
+        &self,
+        chunk: Chunk<Box<dyn Array>>,
+    ) -> arrow2::error::Result<Chunk<Box<dyn Array>>> {
+        let Some(columns) = &self.columns else {
+            return Ok(chunk);
+        };
+
+        let arrays = columns
+            .iter()
+            .map(|(idx, cast)| match cast {
+                Some(dt) => arrow2::compute::cast::cast(
+                    chunk.arrays()[*idx].as_ref(),
+                    dt,
+                    CastOptions::default(),
+                ),
+                None => Ok(chunk.arrays()[*idx].clone()),
+            })
+            .collect::<arrow2::error::Result<Vec<_>>>()?;
+
+        Chunk::try_new(arrays)
+    }
+}
+
+impl<R: ReadSeek> Iterator for AvroChunks<R> {
+    type Item = arrow2::error::Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.reader.next()?;
+
+        Some(chunk.and_then(|c| self.resolve(c)))
+    }
+}
+
+// ================================================================================================
+// AvroReadOptions & AvroWriteOptions
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct AvroReadOptions {
+    /// fields to read, matched by name against the writer schema
+    pub reader_schema: Option<Schema>,
+    /// the (resolved) schema read is validated against it, see `check_schema`
+    pub expected_schema: Option<Schema>,
+}
+
+impl AvroReadOptions {
+    pub fn with_reader_schema(mut self, schema: Schema) -> Self {
+        self.reader_schema = Some(schema);
+        self
+    }
 
-            res = Some(D::from_vec_seq(data));
+    pub fn with_expected_schema(mut self, schema: Schema) -> Self {
+        self.expected_schema = Some(schema);
+        self
+    }
+}
+
+/// Default max number of rows of an Avro block.
+pub const DEFAULT_AVRO_BLOCK_SIZE: usize = 8192;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AvroWriteOptions {
+    pub compression: Option<avro_schema::file::Compression>,
+    /// max number of rows per block, `None` writes a single block
+    pub block_size: Option<usize>,
+}
+
+impl Default for AvroWriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: None,
+            block_size: Some(DEFAULT_AVRO_BLOCK_SIZE),
         }
+    }
+}
+
+impl AvroWriteOptions {
+    pub fn with_compression(mut self, compression: Option<avro_schema::file::Compression>) -> Self {
+        self.compression = compression;
+        self
+    }
 
-        res.unwrap()
+    pub fn with_block_size(mut self, block_size: Option<usize>) -> Self {
+        self.block_size = block_size;
+        self
+    }
+}
+
+// ================================================================================================
+// Arvo async
+// ================================================================================================
+
+type BlockStream<'a> =
+    BoxStream<'a, Result<avro_schema::file::CompressedBlock, avro_schema::error::Error>>;
+
+// `block_stream` borrows the reader, hence the stream owning the reader is created only once
+fn owned_block_stream<'a, R>(mut reader: R, marker: [u8; 16]) -> BlockStream<'a>
+where
+    R: AsyncRead + Unpin + Send + 'a,
+{
+    async_stream::try_stream! {
+        let blocks = avro_schema::read_async::block_stream(&mut reader, marker).await;
+        let mut blocks = std::pin::pin!(blocks);
+        while let Some(block) = blocks.next().await {
+            yield block?;
+        }
+    }
+    .boxed()
+}
+
+struct AvroStreamState<'a> {
+    blocks: BlockStream<'a>,
+    metadata: avro_schema::file::FileMetadata,
+    fields: Vec<Field>,
+    projection: Vec<bool>,
+}
+
+impl AvroStreamState<'_> {
+    async fn next_chunk(&mut self) -> arrow2::error::Result<Option<Chunk<Box<dyn Array>>>> {
+        let Some(mut compressed) = self.blocks.next().await.transpose()? else {
+            return Ok(None);
+        };
+
+        let mut block = avro_schema::file::Block::new(0, vec![]);
+        avro_schema::read_async::decompress_block(
+            &mut compressed,
+            &mut block,
+            self.metadata.compression,
+        )?;
+
+        let chunk = avro_read::deserialize(
+            &block,
+            &self.fields,
+            &self.metadata.record.fields,
+            &self.projection,
+        )?;
+
+        Ok(Some(chunk))
+    }
+}
+
+impl FxIO {
+    pub async fn write_avro_async<D: Eclectic + Purport, W: AsyncWrite + Unpin>(
+        data: D,
+        writer: W,
+        options: Option<avro_schema::file::Compression>,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
+
+        Self::write_avro_stream(schema, futures::stream::iter([data]), writer, None, options).await
+    }
+
+    /// Writes one block per chunk of a stream, chunks longer than `max_rows` are split.
+    pub async fn write_avro_stream<E, S, W>(
+        schema: Schema,
+        data: S,
+        mut writer: W,
+        max_rows: Option<usize>,
+        options: Option<avro_schema::file::Compression>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        S: Stream<Item = E>,
+        W: AsyncWrite + Unpin,
+    {
+        let record = schema_to_record(&schema)?;
+
+        avro_schema::write_async::write_metadata(&mut writer, record.clone(), options).await?;
+
+        let mut data = std::pin::pin!(data);
+        while let Some(d) = data.next().await {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                let compressed_block = chunk_to_block(&chunk, &record, options)?;
+
+                avro_schema::write_async::write_block(&mut writer, &compressed_block).await?;
+            }
+        }
+        writer.flush().await?;
+
+        Ok(())
+    }
+
+    /// Reads all blocks lazily.
+    pub async fn read_avro_stream<'a, D, R>(
+        mut reader: R,
+    ) -> FxResult<FxChunkStream<BoxStream<'a, arrow2::error::Result<Chunk<Box<dyn Array>>>>, D>>
+    where
+        D: Eclectic,
+        R: AsyncRead + Unpin + Send + 'a,
+    {
+        let metadata = avro_schema::read_async::read_metadata(&mut reader).await?;
+        let schema = infer_schema(&metadata.record)?;
+
+        let state = AvroStreamState {
+            blocks: owned_block_stream(reader, metadata.marker),
+            metadata,
+            fields: schema.fields.clone(),
+            projection: vec![true; schema.fields.len()],
+        };
+        let chunks = futures::stream::try_unfold(state, |mut state| async move {
+            Ok(state.next_chunk().await?.map(|c| (c, state)))
+        })
+        .boxed();
+
+        Ok(FxChunkStream::new(schema, chunks))
+    }
+
+    /// Reads all blocks and concatenates them into one `Eclectic`.
+    pub async fn read_avro_async<D, R>(reader: R) -> FxResult<D>
+    where
+        D: Eclectic,
+        R: AsyncRead + Unpin + Send,
+    {
+        Self::read_avro_stream::<D, R>(reader).await?.concat().await
     }
 }
 
@@ -75,24 +534,33 @@ impl FxIO {
 
 impl<T: Eclectic + Purport> SimpleIO<T> {
     pub fn write_arvo(&mut self, options: Option<avro_schema::file::Compression>) -> FxResult<()> {
-        if self.data.is_none() || self.writer.is_none() {
+        self.write_data(|data, writer| FxIO::write_avro(data, writer, options))
+    }
+
+    pub fn write_arvo_with(&mut self, options: AvroWriteOptions) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_avro_with(data, writer, options))
+    }
+
+    pub fn read_arvo(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
             return Err(FxError::EmptyContent);
         }
 
-        let writer = self.writer.take().unwrap();
-        let data = self.take_data().unwrap();
+        let mut reader = self.reader.take().unwrap();
 
-        FxIO::write_avro(data, writer, options)
+        self.data = Some(FxIO::read_avro::<T, _>(&mut reader)?);
+
+        Ok(())
     }
 
-    pub fn read_arvo(&mut self) -> FxResult<()> {
+    pub fn read_arvo_with(&mut self, options: AvroReadOptions) -> FxResult<()> {
         if self.reader.is_none() {
             return Err(FxError::EmptyContent);
         }
 
         let mut reader = self.reader.take().unwrap();
 
-        self.data = Some(FxIO::read_avro::<T, _>(&mut reader)?);
+        self.data = Some(FxIO::read_avro_with::<T, _>(&mut reader, options)?);
 
         Ok(())
     }
@@ -171,4 +639,283 @@ mod test_arvo {
 
         println!("{:?}", ref_data.unwrap());
     }
+
+    #[test]
+    fn avro_read_multi_blocks_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches};
+
+        let batch = FxBatch::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        let record = avro_write::to_record(batch.schema()).unwrap();
+
+        let mut buf = Cursor::new(Vec::new());
+        avro_schema::write::write_metadata(&mut buf, record.clone(), None).unwrap();
+        for _ in 0..2 {
+            let mut serializers = batch
+                .data()
+                .arrays()
+                .iter()
+                .zip(record.fields.iter())
+                .map(|(array, field)| avro_write::new_serializer(array.as_ref(), &field.schema))
+                .collect::<Vec<_>>();
+            let mut block = avro_schema::file::Block::new(3, vec![]);
+            avro_write::serialize(&mut serializers, &mut block);
+            let mut compressed_block = avro_schema::file::CompressedBlock::default();
+            avro_schema::write::compress(&mut block, &mut compressed_block, None).unwrap();
+            avro_schema::write::write_block(&mut buf, &compressed_block).unwrap();
+        }
+
+        buf.set_position(0);
+        let res = FxIO::read_avro::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+
+        buf.set_position(0);
+        let res: FxBatches<ChunkArr> = FxIO::read_avro_into(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data.len(), 2);
+
+        // empty input
+        let res = FxIO::read_avro::<FxBatch, _>(&mut Cursor::new(Vec::<u8>::new()));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn avro_write_blocks_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches};
+
+        let batch = FxBatch::new_with_names(
+            vec![
+                arc_arr!([1, 2, 3, 4, 5]),
+                arc_arr!(["a", "b", "c", "d", "e"]),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = batch.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_avro_iter(schema, [batch.clone(), batch], &mut buf, Some(3), None).unwrap();
+
+        buf.set_position(0);
+        let res: FxBatches<ChunkArr> = FxIO::read_avro_into(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data.len(), 4);
+    }
+
+    #[test]
+    fn avro_write_block_size_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches};
+
+        let batch = FxBatch::new_with_names(
+            vec![
+                arc_arr!([1, 2, 3, 4, 5]),
+                arc_arr!(["a", "b", "c", "d", "e"]),
+            ],
+            ["c1", "c2"],
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        let options = AvroWriteOptions::default().with_block_size(Some(2));
+        FxIO::write_avro_with(batch.clone(), &mut buf, options).unwrap();
+
+        buf.set_position(0);
+        let res: FxBatches<ChunkArr> = FxIO::read_avro_into(&mut buf).unwrap();
+        assert_eq!(res.data.len(), 3);
+
+        buf.set_position(0);
+        let res = FxIO::read_avro::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.data().arrays(), batch.data().arrays());
+
+        // single block
+        let mut buf = Cursor::new(Vec::new());
+        let options = AvroWriteOptions::default().with_block_size(None);
+        FxIO::write_avro_with(batch.clone(), &mut buf, options).unwrap();
+
+        buf.set_position(0);
+        let res: FxBatches<ChunkArr> = FxIO::read_avro_into(&mut buf).unwrap();
+        assert_eq!(res.data.len(), 1);
+
+        let options = AvroWriteOptions::default().with_block_size(Some(0));
+        assert!(FxIO::write_avro_with(batch, Cursor::new(Vec::new()), options).is_err());
+    }
+
+    #[test]
+    fn avro_logical_types_success() {
+        use std::io::Cursor;
+        use std::sync::Arc;
+
+        use arrow2::array::PrimitiveArray;
+        use arrow2::datatypes::TimeUnit;
+        use avro_schema::schema::{LongLogical, Schema as AvroSchema};
+
+        let date =
+            PrimitiveArray::<i32>::from([Some(19000), None, Some(19002)]).to(DataType::Date32);
+        let ts = PrimitiveArray::<i64>::from_slice([1_000_000, 2_000_000, 3_000_000]).to(
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".to_string())),
+        );
+        let local_ts = PrimitiveArray::<i64>::from([Some(1_000_000), Some(2_000_000), None])
+            .to(DataType::Timestamp(TimeUnit::Microsecond, None));
+        let dec = PrimitiveArray::<i128>::from([Some(12345), Some(-100), None])
+            .to(DataType::Decimal(10, 2));
+        let data = FxBatch::new_with_names(
+            vec![
+                Arc::new(date),
+                Arc::new(ts),
+                Arc::new(local_ts),
+                Arc::new(dec),
+            ],
+            ["date", "ts", "local_ts", "dec"],
+        );
+
+        let record = schema_to_record(data.schema()).unwrap();
+        assert_eq!(
+            record.fields[1].schema,
+            AvroSchema::Long(Some(LongLogical::TimestampMicros))
+        );
+        assert_eq!(
+            record.fields[2].schema,
+            AvroSchema::Union(vec![
+                AvroSchema::Null,
+                AvroSchema::Long(Some(LongLogical::LocalTimestampMicros))
+            ])
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_avro(data.clone(), &mut buf, None).unwrap();
+
+        buf.set_position(0);
+        let res = FxIO::read_avro::<FxBatch, _>(&mut buf).unwrap();
+        let types = res
+            .schema()
+            .fields
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Date32,
+                DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".to_string())),
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                DataType::Decimal(10, 2),
+            ]
+        );
+        assert_eq!(res.schema(), data.schema());
+        assert_eq!(res.data(), data.data());
+
+        // other timezones are read back as UTC
+        let ts = |tz: &str| {
+            PrimitiveArray::<i64>::from_slice([1_000_000]).to(DataType::Timestamp(
+                TimeUnit::Millisecond,
+                Some(tz.to_string()),
+            ))
+        };
+        let data = FxBatch::new_with_names(vec![Arc::new(ts("+08:00"))], ["ts"]);
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_avro(data, &mut buf, None).unwrap();
+        buf.set_position(0);
+        let res = FxIO::read_avro::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.data()[0].as_ref(), &ts("+00:00") as &dyn Array);
+    }
+
+    #[test]
+    fn avro_reader_schema_success() {
+        use std::io::Cursor;
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([1, 2, 3]),
+                arc_arr!(["a", "b", "c"]),
+                arc_arr!([Some(1.5), None, Some(3.5)]),
+            ],
+            ["c1", "c2", "c3"],
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_avro_with(
+            data,
+            &mut buf,
+            AvroWriteOptions::default().with_block_size(Some(2)),
+        )
+        .unwrap();
+
+        // reordered & promoted
+        let reader_schema = Schema::from(vec![
+            Field::new("c3", DataType::Float64, true),
+            Field::new("c1", DataType::Int64, false),
+        ]);
+        let options = AvroReadOptions::default().with_reader_schema(reader_schema);
+        buf.set_position(0);
+        let res = FxIO::read_avro_with::<FxBatch, _>(&mut buf, options).unwrap();
+        assert_eq!(res.names(), vec!["c3", "c1"]);
+        assert_eq!(res.schema().fields[1].data_type(), &DataType::Int64);
+        assert_eq!(res.max_len(), Some(3));
+
+        // validated by the expected schema
+        let reader_schema = Schema::from(vec![Field::new("c2", DataType::Utf8, false)]);
+        let options = AvroReadOptions::default()
+            .with_reader_schema(reader_schema)
+            .with_expected_schema(Schema::from(vec![Field::new("c2", DataType::Int32, false)]));
+        buf.set_position(0);
+        assert!(FxIO::read_avro_with::<FxBatch, _>(&mut buf, options).is_err());
+
+        // unknown field
+        let reader_schema = Schema::from(vec![Field::new("c4", DataType::Int32, false)]);
+        let options = AvroReadOptions::default().with_reader_schema(reader_schema);
+        buf.set_position(0);
+        assert!(FxIO::read_avro_with::<FxBatch, _>(&mut buf, options).is_err());
+
+        // incompatible type
+        let reader_schema = Schema::from(vec![Field::new("c2", DataType::Date32, false)]);
+        let options = AvroReadOptions::default().with_reader_schema(reader_schema);
+        buf.set_position(0);
+        assert!(FxIO::read_avro_with::<FxBatch, _>(&mut buf, options).is_err());
+
+        // empty
+        let options = AvroReadOptions::default().with_reader_schema(Schema::from(vec![]));
+        buf.set_position(0);
+        assert!(FxIO::read_avro_with::<FxBatch, _>(&mut buf, options).is_err());
+    }
+
+    #[tokio::test]
+    async fn avro_async_round_trip_success() {
+        use futures::io::Cursor;
+        use futures::TryStreamExt;
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1), None, Some(3)]),
+                arc_arr!(["a", "b", "c"]),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        let stream = futures::stream::iter([data.clone(), data]);
+        FxIO::write_avro_stream(schema, stream, &mut buf, Some(2), None)
+            .await
+            .unwrap();
+
+        buf.set_position(0);
+        let batches = FxIO::read_avro_stream::<FxBatch, _>(&mut buf)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 4);
+
+        buf.set_position(0);
+        let res = FxIO::read_avro_async::<FxBatch, _>(&mut buf).await.unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.max_len(), Some(6));
+    }
 }
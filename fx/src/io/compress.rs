@@ -0,0 +1,469 @@
+//! file: compress.rs
+//! author: Jacob Xie
+//! date: 2026/10/19 00:52:10 Monday
+//! brief: Compressed streams
+
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+
+use super::ec::ReadSeek;
+use crate::error::FxResult;
+
+// ================================================================================================
+// FxCompression
+//
+// Compression of a whole text stream, e.g. `.csv.gz` & `.ndjson.zst`, unlike the block
+// compression of IPC, Avro & Parquet.
+// ================================================================================================
+
+const MAGIC_GZIP: &[u8] = &[0x1f, 0x8b];
+const MAGIC_ZSTD: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FxCompression {
+    Gzip,
+    Zstd,
+}
+
+impl FxCompression {
+    /// Detects the compression by the extension of a path, case-insensitive.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+
+        match ext.as_str() {
+            "gz" | "gzip" => Some(FxCompression::Gzip),
+            "zst" | "zstd" => Some(FxCompression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Detects the compression by the magic bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(MAGIC_GZIP) {
+            Some(FxCompression::Gzip)
+        } else if bytes.starts_with(MAGIC_ZSTD) {
+            Some(FxCompression::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// Decodes a whole compressed stream.
+    pub fn decode<R: Read>(&self, reader: R) -> FxResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        match self {
+            FxCompression::Gzip => MultiGzDecoder::new(reader).read_to_end(&mut buf)?,
+            FxCompression::Zstd => zstd::Decoder::new(reader)?.read_to_end(&mut buf)?,
+        };
+
+        Ok(buf)
+    }
+
+    /// Encodes what `f` writes, then finishes the stream and returns the inner writer.
+    pub fn encode_with<W, F>(&self, writer: W, f: F) -> FxResult<W>
+    where
+        W: Write,
+        F: FnOnce(&mut dyn Write) -> FxResult<()>,
+    {
+        match self {
+            FxCompression::Gzip => {
+                let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+                f(&mut encoder)?;
+                Ok(encoder.finish()?)
+            }
+            FxCompression::Zstd => {
+                let mut encoder = zstd::Encoder::new(writer, 0)?;
+                f(&mut encoder)?;
+                Ok(encoder.finish()?)
+            }
+        }
+    }
+}
+
+// ================================================================================================
+// DecompressReader
+//
+// Decoded on the fly: reading forward streams through the decoder, seeking backward restarts it
+// from the start of the compressed stream, and seeking from the end decodes the whole stream once
+// to learn its length. Hence formats seeking around, e.g. Parquet, are slow when compressed.
+// ================================================================================================
+
+/// A reader decoded if it is gzip or zstd compressed, otherwise read as it is.
+pub struct DecompressReader<R: ReadSeek>(DecompressState<R>);
+
+enum DecompressState<R: ReadSeek> {
+    Plain(R),
+    Decoded(Box<DecodedStream<R>>),
+}
+
+impl<R: ReadSeek> DecompressReader<R> {
+    /// Detects the compression by the magic bytes from the current position.
+    pub fn try_new(mut reader: R) -> FxResult<Self> {
+        let start = reader.stream_position()?;
+
+        let mut magic = Vec::with_capacity(MAGIC_ZSTD.len());
+        reader
+            .by_ref()
+            .take(MAGIC_ZSTD.len() as u64)
+            .read_to_end(&mut magic)?;
+        reader.seek(SeekFrom::Start(start))?;
+
+        let state = match FxCompression::from_bytes(&magic) {
+            Some(c) => DecompressState::Decoded(Box::new(DecodedStream {
+                compression: c,
+                start,
+                decoder: Some(Decoder::new(c, reader)?),
+                pos: 0,
+                len: None,
+            })),
+            None => DecompressState::Plain(reader),
+        };
+
+        Ok(DecompressReader(state))
+    }
+
+    pub fn compression(&self) -> Option<FxCompression> {
+        match &self.0 {
+            DecompressState::Plain(_) => None,
+            DecompressState::Decoded(d) => Some(d.compression),
+        }
+    }
+}
+
+impl<R: ReadSeek> Read for DecompressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.0 {
+            DecompressState::Plain(r) => r.read(buf),
+            DecompressState::Decoded(d) => d.read(buf),
+        }
+    }
+}
+
+impl<R: ReadSeek> Seek for DecompressReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match &mut self.0 {
+            DecompressState::Plain(r) => r.seek(pos),
+            DecompressState::Decoded(d) => d.seek(pos),
+        }
+    }
+}
+
+enum Decoder<R: Read> {
+    Gzip(Box<MultiGzDecoder<R>>),
+    Zstd(zstd::Decoder<'static, BufReader<R>>),
+}
+
+impl<R: Read> Decoder<R> {
+    fn new(compression: FxCompression, reader: R) -> std::io::Result<Self> {
+        match compression {
+            FxCompression::Gzip => Ok(Decoder::Gzip(Box::new(MultiGzDecoder::new(reader)))),
+            FxCompression::Zstd => Ok(Decoder::Zstd(zstd::Decoder::new(reader)?)),
+        }
+    }
+
+    fn into_inner(self) -> R {
+        match self {
+            Decoder::Gzip(d) => (*d).into_inner(),
+            Decoder::Zstd(d) => d.finish().into_inner(),
+        }
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Decoder::Gzip(d) => d.read(buf),
+            Decoder::Zstd(d) => d.read(buf),
+        }
+    }
+}
+
+struct DecodedStream<R: ReadSeek> {
+    compression: FxCompression,
+    // position of the compressed stream in the inner reader
+    start: u64,
+    // `None` only if restarting failed
+    decoder: Option<Decoder<R>>,
+    // position in the decoded stream
+    pos: u64,
+    // length of the decoded stream, known after reaching its end
+    len: Option<u64>,
+}
+
+impl<R: ReadSeek> DecodedStream<R> {
+    fn decoder(&mut self) -> std::io::Result<&mut Decoder<R>> {
+        self.decoder
+            .as_mut()
+            .ok_or_else(|| std::io::Error::other("decoder is lost by a failed seek"))
+    }
+
+    fn restart(&mut self) -> std::io::Result<()> {
+        self.decoder()?;
+        let mut reader = self.decoder.take().unwrap().into_inner();
+        reader.seek(SeekFrom::Start(self.start))?;
+        self.decoder = Some(Decoder::new(self.compression, reader)?);
+        self.pos = 0;
+
+        Ok(())
+    }
+
+    fn skip(&mut self, n: u64) -> std::io::Result<()> {
+        std::io::copy(&mut self.by_ref().take(n), &mut std::io::sink())?;
+
+        Ok(())
+    }
+
+    fn len(&mut self) -> std::io::Result<u64> {
+        if self.len.is_none() {
+            self.skip(u64::MAX)?;
+        }
+
+        Ok(self.len.unwrap_or(self.pos))
+    }
+}
+
+impl<R: ReadSeek> Read for DecodedStream<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.decoder()?.read(buf)?;
+        self.pos += n as u64;
+        if n == 0 && !buf.is_empty() && self.len.is_none() {
+            self.len = Some(self.pos);
+        }
+
+        Ok(n)
+    }
+}
+
+impl<R: ReadSeek> Seek for DecodedStream<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(n) => self.len()?.checked_add_signed(n),
+        }
+        .ok_or_else(|| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })?;
+
+        if target < self.pos {
+            self.restart()?;
+        }
+        self.skip(target - self.pos)?;
+        // beyond the end, reads return nothing
+        self.pos = target;
+
+        Ok(target)
+    }
+}
+
+/// Decodes a buffer if it is gzip or zstd compressed.
+pub(crate) fn decompress_bytes(buf: Vec<u8>) -> FxResult<Vec<u8>> {
+    match FxCompression::from_bytes(&buf) {
+        Some(c) => c.decode(buf.as_slice()),
+        None => Ok(buf),
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_compress {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::ab::{Eclectic, FromSlice, Purport};
+    use crate::arc_arr;
+    use crate::cont::FxBatch;
+    use crate::io::{FxFormat, FxIO, FxWriteOptions, SimpleIO};
+
+    #[test]
+    fn compression_detect_success() {
+        assert_eq!(
+            FxCompression::from_path("a.csv.GZ"),
+            Some(FxCompression::Gzip)
+        );
+        assert_eq!(
+            FxCompression::from_path("a.ndjson.zst"),
+            Some(FxCompression::Zstd)
+        );
+        assert_eq!(FxCompression::from_path("a.csv"), None);
+        assert_eq!(FxFormat::from_path("a.csv.gz"), Some(FxFormat::Csv));
+        assert_eq!(FxFormat::from_path("a.ndjson.zst"), Some(FxFormat::Ndjson));
+
+        for c in [FxCompression::Gzip, FxCompression::Zstd] {
+            let buf = c
+                .encode_with(Vec::new(), |w| Ok(w.write_all(b"c1,c2\n1,a\n")?))
+                .unwrap();
+            assert_eq!(FxCompression::from_bytes(&buf), Some(c));
+
+            let mut reader = DecompressReader::try_new(Cursor::new(buf)).unwrap();
+            assert_eq!(reader.compression(), Some(c));
+            let mut res = String::new();
+            reader.read_to_string(&mut res).unwrap();
+            assert_eq!(res, "c1,c2\n1,a\n");
+        }
+
+        let reader = DecompressReader::try_new(Cursor::new(b"c1".to_vec())).unwrap();
+        assert_eq!(reader.compression(), None);
+    }
+
+    #[test]
+    fn decompress_seek_success() {
+        let buf = FxCompression::Gzip
+            .encode_with(Vec::new(), |w| Ok(w.write_all(b"0123456789")?))
+            .unwrap();
+        let mut reader = DecompressReader::try_new(Cursor::new(buf)).unwrap();
+
+        let mut res = [0u8; 4];
+        reader.read_exact(&mut res).unwrap();
+        assert_eq!(&res, b"0123");
+        assert_eq!(reader.stream_position().unwrap(), 4);
+
+        // forward, backward & from the end
+        reader.seek(SeekFrom::Current(2)).unwrap();
+        reader.read_exact(&mut res).unwrap();
+        assert_eq!(&res, b"6789");
+        reader.seek(SeekFrom::Start(1)).unwrap();
+        reader.read_exact(&mut res).unwrap();
+        assert_eq!(&res, b"1234");
+        assert_eq!(reader.seek(SeekFrom::End(-3)).unwrap(), 7);
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "789");
+
+        // beyond the end
+        assert_eq!(reader.seek(SeekFrom::End(2)).unwrap(), 12);
+        assert_eq!(reader.read(&mut res).unwrap(), 0);
+        assert!(reader.seek(SeekFrom::Current(-13)).is_err());
+    }
+
+    #[test]
+    fn simple_compressed_csv_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+
+        for path in ["./cache/compress.csv.gz", "./cache/compress.csv.zst"] {
+            let mut simple = SimpleIO::new_with_data(data.clone());
+            simple.set_file_writer(path).unwrap();
+            simple.write_csv(None).unwrap();
+
+            let raw = std::fs::read(path).unwrap();
+            assert!(FxCompression::from_bytes(&raw).is_some());
+
+            let mut simple = SimpleIO::<FxBatch>::new();
+            simple.set_file_reader(path).unwrap();
+            simple.read_csv(None).unwrap();
+
+            let res = simple.data().unwrap();
+            assert_eq!(res.names(), vec!["c1", "c2"]);
+            assert_eq!(res.max_len(), Some(3));
+        }
+    }
+
+    #[test]
+    fn simple_compressed_ndjson_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+
+        let path = "./cache/compress.ndjson.zst";
+
+        let mut simple = SimpleIO::new_with_data(data.clone());
+        simple.set_file_writer(path).unwrap();
+        simple.write_ndjson().unwrap();
+
+        let mut simple = SimpleIO::<FxBatch>::new();
+        simple.set_file_reader(path).unwrap();
+        simple.read_ndjson().unwrap();
+        assert_eq!(simple.data().unwrap().max_len(), Some(3));
+
+        // explicit compression regardless of the extension
+        let path = "./cache/compress_explicit.ndjson";
+        let mut simple = SimpleIO::new_with_data(data);
+        simple
+            .set_file_writer_with(path, Some(FxCompression::Gzip))
+            .unwrap();
+        simple.write_ndjson().unwrap();
+
+        // text readers decode transparently
+        let file = std::fs::File::open(path).unwrap();
+        let res = FxIO::read_ndjson::<FxBatch, _>(file).unwrap();
+        assert_eq!(res.max_len(), Some(3));
+
+        let file = std::fs::File::open(path).unwrap();
+        let batches = FxIO::read_ndjson_iter::<FxBatch, _>(file, Some(1), 2)
+            .unwrap()
+            .collect::<FxResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+    }
+
+    #[test]
+    fn compressed_read_write_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+
+        // json
+        let buf = FxCompression::Gzip
+            .encode_with(Vec::new(), |w| FxIO::write_json(data.clone(), w))
+            .unwrap();
+        let res = FxIO::read_json::<FxBatch, _>(buf.as_slice()).unwrap();
+        assert_eq!(res.max_len(), Some(3));
+
+        // csv
+        let buf = FxCompression::Zstd
+            .encode_with(Vec::new(), |w| FxIO::write_csv(data.clone(), w, None))
+            .unwrap();
+        let res = FxIO::read_csv::<FxBatch, _>(Cursor::new(buf.clone()), None).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+
+        let options = crate::io::CsvReadOptions::default().with_batch_size(2);
+        let batches = FxIO::read_csv_iter::<FxBatch, _>(Cursor::new(buf), options)
+            .unwrap()
+            .collect::<FxResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+
+        // unified
+        let path = "./cache/compress_format.csv.gz";
+        FxIO::write(data.clone(), path, FxFormat::Auto).unwrap();
+        assert!(FxCompression::from_bytes(&std::fs::read(path).unwrap()).is_some());
+        let res: FxBatch = FxIO::read(path, FxFormat::Auto).unwrap();
+        assert_eq!(res.schema().fields.len(), 2);
+
+        let options = FxWriteOptions::default().with_compression(FxCompression::Zstd);
+        let mut buf = Vec::new();
+        FxIO::write_with(
+            data,
+            crate::io::FxSink::writer(&mut buf),
+            FxFormat::Ndjson,
+            options,
+        )
+        .unwrap();
+        let source = crate::io::FxSource::reader(Cursor::new(buf));
+        let res: FxBatch = FxIO::read(source, FxFormat::Auto).unwrap();
+        assert_eq!(res.max_len(), Some(3));
+    }
+}
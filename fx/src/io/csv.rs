@@ -3,15 +3,26 @@
 //! date: 2023/03/13 22:53:42 Monday
 //! brief: CSV I/O
 
-use std::io::Write;
-// use std::sync::mpsc as std_mpsc;
+use std::collections::{HashSet, VecDeque};
+use std::io::{Read, Write};
 
+use arrow2::array::Array;
+use arrow2::bitmap::Bitmap;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::io::csv::read as csv_read;
+use arrow2::io::csv::read_async as csv_read_async;
 use arrow2::io::csv::write as csv_write;
 
-use super::{ec::ReadSeek, FxIO, ParallelIO, SimpleIO};
-use crate::ab::{Congruent, Eclectic, FxSeq, Purport};
+use super::compress::DecompressReader;
+use super::ec::{par_map_ordered, ReadSeek};
+use super::{FxIO, ParallelIO, SimpleIO};
+use futures::stream::BoxStream;
+use futures::{AsyncRead, AsyncWrite, AsyncWriteExt, Stream, StreamExt};
+
+use crate::ab::{Congruent, Eclectic, FxChunkIter, FxChunkStream, IntoChunks, Purport};
 use crate::error::{FxError, FxResult};
+use crate::io::ab::eclectic_to_sized_chunks;
 
 // ================================================================================================
 // CSV
@@ -35,31 +46,563 @@ impl FxIO {
         Ok(())
     }
 
+    /// Writes chunks one by one, chunks longer than `max_rows` are split.
+    pub fn write_csv_iter<E, I, W>(
+        schema: Schema,
+        data: I,
+        mut writer: W,
+        max_rows: Option<usize>,
+        options: Option<&csv_write::SerializeOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let names = schema
+            .fields
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        let default_opt = csv_write::SerializeOptions::default();
+        let opt = options.unwrap_or(&default_opt);
+
+        csv_write::write_header(&mut writer, &names, opt)?;
+
+        for d in data {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                csv_write::write_chunk(&mut writer, &chunk, opt)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes chunks of a receptacle one by one, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn write_csv_from<C: IntoChunks, W: Write>(
+        data: C,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<&csv_write::SerializeOptions>,
+    ) -> FxResult<()> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_csv_iter(schema, chunks, writer, max_rows, options)
+    }
+
+    /// Reads batches of `options.batch_size` rows lazily until EOF, gzip or zstd compressed
+    /// input is decoded.
+    pub fn read_csv_iter<D: Eclectic, R: ReadSeek>(
+        reader: R,
+        options: CsvReadOptions,
+    ) -> FxResult<FxChunkIter<CsvChunks<DecompressReader<R>>, D>> {
+        let (schema, chunks) = CsvChunks::try_new(DecompressReader::try_new(reader)?, options)?;
+
+        Ok(FxChunkIter::new(schema, chunks))
+    }
+
+    /// Reads the whole file into one `Eclectic`, gzip or zstd compressed files are decoded.
     pub fn read_csv<D: Eclectic + Purport, R: ReadSeek>(
         reader: R,
         projection: Option<&[usize]>,
     ) -> FxResult<D> {
-        let mut cr = csv_read::ReaderBuilder::new().from_reader(reader);
-        let (fields, s) = csv_read::infer_schema(&mut cr, None, true, &csv_read::infer)?;
+        let options = CsvReadOptions {
+            projection: projection.map(<[usize]>::to_vec),
+            ..Default::default()
+        };
 
-        let mut rows = vec![csv_read::ByteRecord::default(); s];
+        Self::read_csv_iter::<D, _>(reader, options)?.concat()
+    }
+}
 
-        let rows_read = csv_read::read_rows(&mut cr, 0, &mut rows)?;
-        let rows = &rows[..rows_read];
+// ================================================================================================
+// CsvReadOptions
+// ================================================================================================
 
-        let res = csv_read::deserialize_batch(
-            rows,
-            &fields,
-            projection,
-            0,
-            csv_read::deserialize_column,
-        )?
-        .into_arrays()
+#[derive(Debug, Clone)]
+pub struct CsvReadOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub has_header: bool,
+    /// cells equal to any of these tokens are read as null, empty cells are always null
+    pub null_tokens: Vec<String>,
+    /// the complete schema, skips inference
+    pub schema: Option<Schema>,
+    /// fields replacing the inferred ones with the same names
+    pub schema_overrides: Option<Schema>,
+    /// max number of rows used for inference, `None` means the whole file
+    pub infer_rows: Option<usize>,
+    /// number of rows per batch
+    pub batch_size: usize,
+    pub projection: Option<Vec<usize>>,
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+            null_tokens: Vec::new(),
+            schema: None,
+            schema_overrides: None,
+            infer_rows: Some(1000),
+            batch_size: 8192,
+            projection: None,
+        }
+    }
+}
+
+impl CsvReadOptions {
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_null_tokens<I, T>(mut self, tokens: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.null_tokens = tokens.into_iter().map(|t| t.as_ref().to_string()).collect();
+        self
+    }
+
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn with_schema_overrides(mut self, schema: Schema) -> Self {
+        self.schema_overrides = Some(schema);
+        self
+    }
+
+    pub fn with_infer_rows(mut self, infer_rows: Option<usize>) -> Self {
+        self.infer_rows = infer_rows;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+}
+
+// ================================================================================================
+// CsvChunks
+//
+// Rows used for inference are buffered rather than re-read, hence the reader needs not `Seek`.
+// ================================================================================================
+
+pub struct CsvChunks<R: Read> {
+    reader: csv_read::Reader<R>,
+    fields: Vec<Field>,
+    projection: Option<Vec<usize>>,
+    null_tokens: Vec<String>,
+    buffered: VecDeque<csv_read::ByteRecord>,
+    batch_size: usize,
+    line: usize,
+}
+
+fn header_names<'a, I: Iterator<Item = &'a [u8]>>(headers: I, has_header: bool) -> Vec<String> {
+    headers
+        .enumerate()
+        .map(|(i, h)| match has_header {
+            true => String::from_utf8_lossy(h).to_string(),
+            false => format!("Col_{i}"),
+        })
+        .collect()
+}
+
+fn is_null_token(cell: Option<&[u8]>, null_tokens: &[String]) -> bool {
+    match cell {
+        Some(c) => c.is_empty() || null_tokens.iter().any(|t| t.as_bytes() == c),
+        None => true,
+    }
+}
+
+fn infer_record<'a, F>(types: &mut [HashSet<DataType>], cell: F, null_tokens: &[String])
+where
+    F: Fn(usize) -> Option<&'a [u8]>,
+{
+    for (col, t) in types.iter_mut().enumerate() {
+        let c = cell(col);
+        if !is_null_token(c, null_tokens) {
+            t.insert(csv_read::infer(c.unwrap()));
+        }
+    }
+}
+
+// same as arrow2's csv inference: integers & floats merge into floats, other conflicts into strings
+fn merge_types(types: &HashSet<DataType>) -> DataType {
+    match types.len() {
+        1 => types.iter().next().unwrap().clone(),
+        2 if types.contains(&DataType::Int64) && types.contains(&DataType::Float64) => {
+            DataType::Float64
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+fn inferred_fields(names: Vec<String>, types: &[HashSet<DataType>]) -> Vec<Field> {
+    names
         .into_iter()
-        .map(FxSeq::from_box_arr)
+        .zip(types.iter())
+        .map(|(n, t)| Field::new(n, merge_types(t), true))
+        .collect()
+}
+
+// applies `schema_overrides` to `fields`, and returns the projected schema
+fn resolve_schema(fields: &mut [Field], options: &CsvReadOptions) -> FxResult<Schema> {
+    if let Some(overrides) = &options.schema_overrides {
+        for o in overrides.fields.iter() {
+            let f = fields
+                .iter_mut()
+                .find(|f| f.name == o.name)
+                .ok_or_else(|| {
+                    FxError::InvalidArgument(format!("column `{}` not found", o.name))
+                })?;
+            *f = o.clone();
+        }
+    }
+
+    let projected = match &options.projection {
+        Some(p) => p
+            .iter()
+            .map(|&i| fields.get(i).cloned().ok_or(FxError::OutBounds))
+            .collect::<FxResult<Vec<_>>>()?,
+        None => fields.to_vec(),
+    };
+
+    Ok(Schema::from(projected))
+}
+
+fn mask_null_tokens<'a, I>(
+    array: Box<dyn Array>,
+    cells: I,
+    null_tokens: &[String],
+) -> Box<dyn Array>
+where
+    I: Iterator<Item = Option<&'a [u8]>>,
+{
+    // empty cells are null with or without `null_tokens`, arrow2 reads them as `""` for strings
+    let nulls = cells
+        .map(|c| is_null_token(c, null_tokens))
         .collect::<Vec<_>>();
+    if !nulls.iter().any(|n| *n) {
+        return array;
+    }
 
-        D::from_vec_seq(res)
+    let validity = match array.validity() {
+        Some(v) => v.iter().zip(nulls).map(|(v, n)| v && !n).collect(),
+        None => nulls.into_iter().map(|n| !n).collect::<Bitmap>(),
+    };
+
+    array.with_validity(Some(validity))
+}
+
+fn check_batch_size(options: &CsvReadOptions) -> FxResult<()> {
+    if options.batch_size == 0 {
+        return Err(FxError::InvalidArgument(
+            "batch size should be greater than 0".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+impl<R: Read> CsvChunks<R> {
+    /// Returns the (projected) schema and the chunk iterator.
+    pub fn try_new(reader: R, options: CsvReadOptions) -> FxResult<(Schema, Self)> {
+        check_batch_size(&options)?;
+
+        let mut reader = csv_read::ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .has_headers(options.has_header)
+            .from_reader(reader);
+        let mut buffered = VecDeque::new();
+
+        let mut fields = match &options.schema {
+            Some(s) => s.fields.clone(),
+            None => {
+                let headers = reader.byte_headers().map_err(arrow2::error::Error::from)?;
+                let names = header_names(headers.iter(), options.has_header);
+
+                let mut types = vec![HashSet::new(); names.len()];
+                let max_rows = options.infer_rows.unwrap_or(usize::MAX);
+                while buffered.len() < max_rows {
+                    let mut record = csv_read::ByteRecord::new();
+                    if !reader
+                        .read_byte_record(&mut record)
+                        .map_err(arrow2::error::Error::from)?
+                    {
+                        break;
+                    }
+                    infer_record(&mut types, |c| record.get(c), &options.null_tokens);
+                    buffered.push_back(record);
+                }
+
+                inferred_fields(names, &types)
+            }
+        };
+        let schema = resolve_schema(&mut fields, &options)?;
+
+        let chunks = Self {
+            reader,
+            fields,
+            projection: options.projection,
+            null_tokens: options.null_tokens,
+            buffered,
+            batch_size: options.batch_size,
+            // 0-based line of the next row, the header occupies the first line
+            line: usize::from(options.has_header),
+        };
+
+        Ok((schema, chunks))
+    }
+}
+
+impl<R: Read> Iterator for CsvChunks<R> {
+    type Item = arrow2::error::Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rows = Vec::new();
+        while rows.len() < self.batch_size {
+            if let Some(r) = self.buffered.pop_front() {
+                rows.push(r);
+                continue;
+            }
+
+            let mut record = csv_read::ByteRecord::new();
+            match self.reader.read_byte_record(&mut record) {
+                Ok(true) => rows.push(record),
+                Ok(false) => break,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let null_tokens = &self.null_tokens;
+        let res = csv_read::deserialize_batch(
+            &rows,
+            &self.fields,
+            self.projection.as_deref(),
+            self.line,
+            |rows, col, datatype, line| {
+                let array = csv_read::deserialize_column(rows, col, datatype, line)?;
+                Ok(mask_null_tokens(
+                    array,
+                    rows.iter().map(|r| r.get(col)),
+                    null_tokens,
+                ))
+            },
+        );
+        self.line += rows.len();
+
+        Some(res)
+    }
+}
+
+// ================================================================================================
+// CSV async
+// ================================================================================================
+
+struct CsvStreamState<R: AsyncRead + Unpin + Send> {
+    reader: csv_read_async::AsyncReader<R>,
+    fields: Vec<Field>,
+    projection: Option<Vec<usize>>,
+    null_tokens: Vec<String>,
+    buffered: VecDeque<csv_read_async::ByteRecord>,
+    batch_size: usize,
+    line: usize,
+}
+
+impl<R: AsyncRead + Unpin + Send> CsvStreamState<R> {
+    async fn next_chunk(&mut self) -> arrow2::error::Result<Option<Chunk<Box<dyn Array>>>> {
+        let mut rows = Vec::new();
+        while rows.len() < self.batch_size {
+            if let Some(r) = self.buffered.pop_front() {
+                rows.push(r);
+                continue;
+            }
+
+            let mut record = csv_read_async::ByteRecord::new();
+            if !self.reader.read_byte_record(&mut record).await? {
+                break;
+            }
+            rows.push(record);
+        }
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let null_tokens = &self.null_tokens;
+        let chunk = csv_read_async::deserialize_batch(
+            &rows,
+            &self.fields,
+            self.projection.as_deref(),
+            self.line,
+            |rows, col, datatype, line| {
+                let array = csv_read_async::deserialize_column(rows, col, datatype, line)?;
+                Ok(mask_null_tokens(
+                    array,
+                    rows.iter().map(|r| r.get(col)),
+                    null_tokens,
+                ))
+            },
+        )?;
+        self.line += rows.len();
+
+        Ok(Some(chunk))
+    }
+}
+
+impl FxIO {
+    /// Reads batches of `options.batch_size` rows lazily until EOF.
+    pub async fn read_csv_stream<'a, D, R>(
+        reader: R,
+        options: CsvReadOptions,
+    ) -> FxResult<FxChunkStream<BoxStream<'a, arrow2::error::Result<Chunk<Box<dyn Array>>>>, D>>
+    where
+        D: Eclectic,
+        R: AsyncRead + Unpin + Send + 'a,
+    {
+        check_batch_size(&options)?;
+
+        let mut reader = csv_read_async::AsyncReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .has_headers(options.has_header)
+            .create_reader(reader);
+        let mut buffered = VecDeque::new();
+
+        let mut fields = match &options.schema {
+            Some(s) => s.fields.clone(),
+            None => {
+                let headers = reader
+                    .byte_headers()
+                    .await
+                    .map_err(arrow2::error::Error::from)?;
+                let names = header_names(headers.iter(), options.has_header);
+
+                let mut types = vec![HashSet::new(); names.len()];
+                let max_rows = options.infer_rows.unwrap_or(usize::MAX);
+                while buffered.len() < max_rows {
+                    let mut record = csv_read_async::ByteRecord::new();
+                    if !reader
+                        .read_byte_record(&mut record)
+                        .await
+                        .map_err(arrow2::error::Error::from)?
+                    {
+                        break;
+                    }
+                    infer_record(&mut types, |c| record.get(c), &options.null_tokens);
+                    buffered.push_back(record);
+                }
+
+                inferred_fields(names, &types)
+            }
+        };
+        let schema = resolve_schema(&mut fields, &options)?;
+
+        let state = CsvStreamState {
+            reader,
+            fields,
+            projection: options.projection,
+            null_tokens: options.null_tokens,
+            buffered,
+            batch_size: options.batch_size,
+            // 0-based line of the next row, the header occupies the first line
+            line: usize::from(options.has_header),
+        };
+        let chunks = futures::stream::try_unfold(state, |mut state| async move {
+            Ok(state.next_chunk().await?.map(|c| (c, state)))
+        })
+        .boxed();
+
+        Ok(FxChunkStream::new(schema, chunks))
+    }
+
+    /// Reads the whole file into one `Eclectic`.
+    pub async fn read_csv_async<D, R>(reader: R, options: CsvReadOptions) -> FxResult<D>
+    where
+        D: Eclectic,
+        R: AsyncRead + Unpin + Send,
+    {
+        Self::read_csv_stream::<D, R>(reader, options)
+            .await?
+            .concat()
+            .await
+    }
+
+    pub async fn write_csv_async<D: Eclectic + Purport, W: AsyncWrite + Unpin>(
+        data: D,
+        writer: W,
+        options: Option<&csv_write::SerializeOptions>,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
+
+        Self::write_csv_stream(schema, futures::stream::iter([data]), writer, None, options).await
+    }
+
+    /// Writes chunks of a stream one by one, chunks longer than `max_rows` are split.
+    /// Every chunk is serialized in memory before being written.
+    pub async fn write_csv_stream<E, S, W>(
+        schema: Schema,
+        data: S,
+        mut writer: W,
+        max_rows: Option<usize>,
+        options: Option<&csv_write::SerializeOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        S: Stream<Item = E>,
+        W: AsyncWrite + Unpin,
+    {
+        let names = schema
+            .fields
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        let default_opt = csv_write::SerializeOptions::default();
+        let opt = options.unwrap_or(&default_opt);
+
+        let mut buf = Vec::new();
+        csv_write::write_header(&mut buf, &names, opt)?;
+        writer.write_all(&buf).await?;
+
+        let mut data = std::pin::pin!(data);
+        while let Some(d) = data.next().await {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                buf.clear();
+                csv_write::write_chunk(&mut buf, &chunk, opt)?;
+                writer.write_all(&buf).await?;
+            }
+        }
+        writer.flush().await?;
+
+        Ok(())
     }
 }
 
@@ -70,14 +613,7 @@ impl FxIO {
 impl<T: Eclectic + Purport> SimpleIO<T> {
     // notice after writing complete, data & writer both turn to None
     pub fn write_csv(&mut self, options: Option<&csv_write::SerializeOptions>) -> FxResult<()> {
-        if self.data.is_none() || self.writer.is_none() {
-            return Err(FxError::EmptyContent);
-        }
-
-        let mut writer = self.writer.take().unwrap();
-        let data = self.take_data().unwrap();
-
-        FxIO::write_csv(data, &mut writer, options)
+        self.write_data(|data, writer| FxIO::write_csv(data, writer, options))
     }
 
     pub fn read_csv(&mut self, projection: Option<&[usize]>) -> FxResult<()> {
@@ -98,29 +634,38 @@ impl<T: Eclectic + Purport> SimpleIO<T> {
 // ================================================================================================
 
 impl ParallelIO {
+    /// Each aligned row batch of the `FxTabular` is serialized on a worker thread, and the
+    /// serialized bytes are written to the writer in order.
     pub fn write_csv(&mut self, options: Option<&csv_write::SerializeOptions>) -> FxResult<()> {
         if self.data.is_none() || self.writer.is_none() {
             return Err(FxError::EmptyContent);
         }
 
-        let mut _write = self.writer.take().unwrap();
-        let _data = self.task_data().unwrap();
-
-        // channel
-        // let (tx, rx): (std_mpsc::Sender<_>, std_mpsc::Receiver<_>) = std_mpsc::channel();
-        // let mut children = Vec::new();
-
-        // TODO: get `parallel_num` from `FxTabular` chunks
-        let parallel_num = 0;
-
-        (0..parallel_num).for_each(|_id| {
-            // let thread_tx = tx.clone();
+        let parallel_num = self.parallel_num();
+        let mut writer = self.writer.take().unwrap();
+        let data = self.task_data().unwrap();
+
+        let (schema, chunks) = data.into_chunks()?;
+        let names = schema
+            .fields
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        let default_opt = csv_write::SerializeOptions::default();
+        let opt = options.unwrap_or(&default_opt);
 
-            let _options = options.clone();
-            // let batch =
-        });
+        csv_write::write_header(&mut writer, &names, opt)?;
 
-        Ok(())
+        par_map_ordered(
+            chunks,
+            parallel_num,
+            |chunk| {
+                let mut buf = Vec::new();
+                csv_write::write_chunk(&mut buf, &chunk, opt)?;
+                Ok(buf)
+            },
+            |buf| Ok(writer.write_all(&buf)?),
+        )
     }
 }
 
@@ -197,4 +742,208 @@ mod test_csv {
 
         println!("{:?}", ref_data.unwrap());
     }
+
+    #[test]
+    fn csv_write_chunks_success() {
+        use crate::ab::{Dqs, Receptacle};
+        use crate::cont::{ChunkArr, FxTable};
+
+        let mut table = FxTable::<2>::new_with_names(
+            vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])],
+            ["c1", "c2"],
+        );
+        table
+            .push(ChunkArr::new(vec![arc_arr!([3]), arc_arr!(["c"])]))
+            .unwrap();
+
+        let mut buf = Vec::new();
+        FxIO::write_csv_from(table, &mut buf, Some(1), None).unwrap();
+
+        assert_eq!(String::from_utf8(buf).unwrap(), "c1,c2\n1,a\n2,b\n3,c\n");
+    }
+
+    #[test]
+    fn csv_read_iter_success() {
+        use crate::ab::FxSeq;
+        use crate::value::FxValue;
+
+        let csv = "id;name;price\n1;a;1.5\n2;NA;2\n3;c;NA\n4;d;4.5\n5;e;5\n";
+        let overrides = Schema::from(vec![Field::new("id", DataType::Int32, true)]);
+        let options = CsvReadOptions::default()
+            .with_delimiter(b';')
+            .with_null_tokens(["NA"])
+            .with_schema_overrides(overrides)
+            .with_infer_rows(Some(3))
+            .with_batch_size(2);
+
+        let iter = FxIO::read_csv_iter::<FxBatch, _>(std::io::Cursor::new(csv), options).unwrap();
+        let schema = iter.schema().clone();
+        assert_eq!(schema.fields[0].data_type(), &DataType::Int32);
+        assert_eq!(schema.fields[1].data_type(), &DataType::Utf8);
+        assert_eq!(schema.fields[2].data_type(), &DataType::Float64);
+
+        let batches = iter.collect::<FxResult<Vec<_>>>().unwrap();
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[2].max_len(), Some(1));
+        assert_eq!(batches[0].schema().fields[1].name, "name");
+        assert_eq!(
+            batches[0].data()[1].to_values().unwrap(),
+            vec![FxValue::from("a"), FxValue::Null]
+        );
+        assert_eq!(
+            batches[1].data()[2].to_values().unwrap(),
+            vec![FxValue::Null, FxValue::F64(4.5)]
+        );
+    }
+
+    #[test]
+    fn csv_read_empty_cells_success() {
+        use crate::ab::FxSeq;
+        use crate::value::FxValue;
+
+        let csv = "id,name\n1,\n2,NA\n3,c\n";
+        for tokens in [vec![], vec!["NA"]] {
+            let options = CsvReadOptions::default().with_null_tokens(tokens.clone());
+            let batch = FxIO::read_csv_iter::<FxBatch, _>(std::io::Cursor::new(csv), options)
+                .unwrap()
+                .concat()
+                .unwrap();
+            let na = match tokens.is_empty() {
+                true => FxValue::from("NA"),
+                false => FxValue::Null,
+            };
+            assert_eq!(
+                batch.data()[1].to_values().unwrap(),
+                vec![FxValue::Null, na, FxValue::from("c")]
+            );
+        }
+    }
+
+    #[test]
+    fn csv_chunks_line_success() {
+        let csv = "id,name\n1,a\n2,b\n3,c\n";
+        let options = CsvReadOptions::default().with_batch_size(2);
+        let (_, mut chunks) = CsvChunks::try_new(std::io::Cursor::new(csv), options).unwrap();
+        assert_eq!(chunks.line, 1);
+        assert!(chunks.next().unwrap().is_ok());
+        assert_eq!(chunks.line, 3);
+
+        let options = CsvReadOptions::default().with_header(false);
+        let (_, chunks) = CsvChunks::try_new(std::io::Cursor::new(csv), options).unwrap();
+        assert_eq!(chunks.line, 0);
+    }
+
+    #[test]
+    fn csv_read_no_header_projection_success() {
+        let csv = "1,x\n2,y\n";
+        let options = CsvReadOptions::default()
+            .with_header(false)
+            .with_projection(vec![1]);
+
+        let batch = FxIO::read_csv_iter::<FxBatch, _>(std::io::Cursor::new(csv), options)
+            .unwrap()
+            .concat()
+            .unwrap();
+        assert_eq!(batch.schema.fields.len(), 1);
+        assert_eq!(batch.schema.fields[0].name, "Col_1");
+        assert_eq!(batch.max_len(), Some(2));
+    }
+
+    #[test]
+    fn parallel_write_success() {
+        use crate::ab::Dqs;
+        use crate::cont::FxTabular;
+
+        let mut data =
+            FxTabular::new_with_names(vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])], ["c1", "c2"]);
+        data.push_back(vec![arc_arr!([3]), arc_arr!(["c"])])
+            .unwrap();
+        data.push_back(vec![arc_arr!([4, 5]), arc_arr!(["d", "e"])])
+            .unwrap();
+
+        let file = "./cache/test_parallel.csv";
+        let mut parallel = ParallelIO::new_with_data(data);
+        parallel.set_parallel_num(2).unwrap();
+        parallel.set_file_writer(file).unwrap();
+        parallel.write_csv(None).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(file).unwrap(),
+            "c1,c2\n1,a\n2,b\n3,c\n4,d\n5,e\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn csv_async_round_trip_success() {
+        use futures::io::Cursor;
+        use futures::TryStreamExt;
+
+        use crate::cont::FxBatches;
+
+        let data = FxBatch::new_with_names(
+            vec![arc_arr!([1i64, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        let stream = futures::stream::iter([data.clone(), data]);
+        FxIO::write_csv_stream(schema, stream, &mut buf, Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buf.get_ref().clone()).unwrap(),
+            "c1,c2\n1,a\n2,b\n3,c\n1,a\n2,b\n3,c\n"
+        );
+
+        buf.set_position(0);
+        let options = CsvReadOptions::default().with_batch_size(4);
+        let batches = FxIO::read_csv_stream::<FxBatch, _>(&mut buf, options)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].max_len(), Some(2));
+        assert_eq!(batches[0].schema().fields[0].data_type(), &DataType::Int64);
+
+        buf.set_position(0);
+        let res: FxBatches<FxBatch> =
+            FxIO::read_csv_stream(&mut buf, CsvReadOptions::default().with_batch_size(4))
+                .await
+                .unwrap()
+                .collect_into()
+                .await
+                .unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+    }
+
+    #[tokio::test]
+    async fn csv_async_read_empty_cells_success() {
+        use futures::io::Cursor;
+        use futures::TryStreamExt;
+
+        use crate::ab::FxSeq;
+        use crate::value::FxValue;
+
+        let csv = "id,name\n1,\n2,NA\n3,c\n";
+        for tokens in [vec![], vec!["NA"]] {
+            let na = match tokens.is_empty() {
+                true => FxValue::from("NA"),
+                false => FxValue::Null,
+            };
+            let options = CsvReadOptions::default().with_null_tokens(tokens);
+            let batches = FxIO::read_csv_stream::<FxBatch, _>(Cursor::new(csv), options)
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            assert_eq!(
+                batches[0].data()[1].to_values().unwrap(),
+                vec![FxValue::Null, na, FxValue::from("c")]
+            );
+        }
+    }
 }
@@ -0,0 +1,730 @@
+//! file: dataset.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 23:41:17 Sunday
+//! brief: Hive partitioned Parquet dataset
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use arrow2::array::{get_display, new_null_array, Array, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::compute::filter::filter_chunk;
+use arrow2::compute::take::take;
+use arrow2::datatypes::{DataType, Field, Schema};
+
+use super::parquet::{ParquetReadOptions, ParquetWriteOptions};
+use super::predicate::{array_values, ColumnStats, FxPredicate};
+use super::FxIO;
+use crate::ab::{Eclectic, FxChunkIter, IntoChunks, Purport};
+use crate::cont::{ArcArr, ChunkArr, FxBatch, FxBatches};
+use crate::error::{FxError, FxResult};
+use crate::io::ab::eclectic_to_sized_chunks;
+use crate::value::FxValue;
+
+/// Directory name of null partition values.
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+// formatted values of the partition columns, `None` for nulls
+type PartitionKey = Vec<Option<String>>;
+
+// ================================================================================================
+// Options
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct DatasetWriteOptions {
+    /// partition columns, in the order of directory levels
+    pub partition_by: Vec<String>,
+    /// rows per row group
+    pub max_rows: Option<usize>,
+    pub parquet: ParquetWriteOptions,
+}
+
+impl DatasetWriteOptions {
+    pub fn new<I, T>(partition_by: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        Self {
+            partition_by: partition_by
+                .into_iter()
+                .map(|n| n.as_ref().to_string())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    pub fn with_parquet_options(mut self, options: ParquetWriteOptions) -> Self {
+        self.parquet = options;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DatasetReadOptions {
+    /// partitions are pruned by their values, and the remaining rows are filtered
+    pub predicate: Option<FxPredicate>,
+    /// types of partition columns, the others are inferred as `Int64`, `Float64` or `Utf8`
+    pub partition_schema: Option<Schema>,
+}
+
+impl DatasetReadOptions {
+    pub fn with_predicate(mut self, predicate: FxPredicate) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    pub fn with_partition_schema(mut self, schema: Schema) -> Self {
+        self.partition_schema = Some(schema);
+        self
+    }
+}
+
+// ================================================================================================
+// Dataset
+//
+// Layout: `root/k1=v1/k2=v2/part-N.parquet`. Partition columns are stored in paths only, and they
+// are appended after the file columns when reading.
+// ================================================================================================
+
+impl FxIO {
+    /// Writes one new file per partition, returns the written files.
+    pub fn write_dataset<D: Eclectic + Purport, P: AsRef<Path>>(
+        data: D,
+        root: P,
+        options: &DatasetWriteOptions,
+    ) -> FxResult<Vec<PathBuf>> {
+        let schema = data.schema().clone();
+
+        write_partitions(schema, eclectic_to_sized_chunks(data, None)?, root, options)
+    }
+
+    /// Writes one new file per partition for all the chunks of a receptacle, e.g. `FxTabular`.
+    pub fn write_dataset_from<C: IntoChunks, P: AsRef<Path>>(
+        data: C,
+        root: P,
+        options: &DatasetWriteOptions,
+    ) -> FxResult<Vec<PathBuf>> {
+        let (schema, chunks) = data.into_chunks()?;
+        let chunks = chunks
+            .into_iter()
+            .map(|c| eclectic_to_sized_chunks(c, None))
+            .collect::<FxResult<Vec<_>>>()?;
+
+        write_partitions(schema, chunks.into_iter().flatten(), root, options)
+    }
+
+    /// Reads all the files under `root`, one batch per row group.
+    pub fn read_dataset<P: AsRef<Path>>(
+        root: P,
+        options: DatasetReadOptions,
+    ) -> FxResult<FxBatches<FxBatch>> {
+        let root = root.as_ref();
+        let mut files = Vec::new();
+        discover_files(root, &mut files)?;
+        files.sort();
+
+        let parts = files
+            .iter()
+            .map(|f| parse_partition(root, f))
+            .collect::<FxResult<Vec<_>>>()?;
+        let names = match parts.first() {
+            Some(p) => p.iter().map(|(k, _)| k.clone()).collect::<Vec<_>>(),
+            None => return Err(FxError::EmptyContent),
+        };
+        if parts
+            .iter()
+            .any(|p| p.len() != names.len() || p.iter().zip(&names).any(|((k, _), n)| k != n))
+        {
+            return Err(FxError::InvalidArgument(
+                "files should be partitioned by the same columns".to_string(),
+            ));
+        }
+
+        let partition_fields = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let given = options
+                    .partition_schema
+                    .as_ref()
+                    .and_then(|s| s.fields.iter().find(|f| &f.name == name));
+                match given {
+                    Some(f) => f.clone(),
+                    None => Field::new(name, infer_type(parts.iter().map(|p| &p[i].1)), true),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // literals of typed partition columns are parsed once for pruning
+        let pruning = options
+            .predicate
+            .as_ref()
+            .map(|p| p.resolve(&Schema::from(partition_fields.clone())))
+            .transpose()?;
+
+        let mut schema: Option<Schema> = None;
+        let mut chunks: Vec<FxResult<Chunk<Box<dyn Array>>>> = Vec::new();
+        for (file, part) in files.iter().zip(parts) {
+            let values = partition_fields
+                .iter()
+                .zip(&part)
+                .map(|(f, (_, v))| partition_value(v.as_deref(), f.data_type()))
+                .collect::<FxResult<Vec<_>>>()?;
+
+            // partition pruning, values are seen as statistics of single-valued columns
+            if let Some(predicate) = &pruning {
+                let stats = partition_stats(&partition_fields, &values)?;
+                if !predicate.might_match(&stats) {
+                    continue;
+                }
+            }
+
+            let mut reader = File::open(file)?;
+            let file_schema = read_file_schema(&mut reader)?;
+            let full_schema = Schema::from(
+                file_schema
+                    .fields
+                    .iter()
+                    .chain(&partition_fields)
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+            match &schema {
+                Some(s) if s.fields != full_schema.fields => {
+                    return Err(FxError::InvalidArgument(format!(
+                        "schema of `{}` mismatched",
+                        file.display()
+                    )))
+                }
+                Some(_) => {}
+                None => schema = Some(full_schema.clone()),
+            }
+
+            // predicates on file columns only are pushed down to the Parquet reader
+            let (parquet_options, post_filter) = match &options.predicate {
+                Some(p)
+                    if p.columns()
+                        .iter()
+                        .all(|c| file_schema.fields.iter().any(|f| &f.name == c)) =>
+                {
+                    (
+                        ParquetReadOptions::default().with_predicate(p.clone()),
+                        None,
+                    )
+                }
+                p => (ParquetReadOptions::default(), p.as_ref()),
+            };
+
+            let iter = FxIO::read_parquet_iter_with::<ChunkArr, _>(&mut reader, parquet_options)?;
+            for chunk in iter {
+                let mut arrays = chunk?
+                    .into_arrays()
+                    .into_iter()
+                    .map(|a| a.to_boxed())
+                    .collect::<Vec<_>>();
+                let len = arrays.first().map_or(0, |a| a.len());
+                for (v, f) in values.iter().zip(&partition_fields) {
+                    arrays.push(repeat_value(v.as_deref(), f.data_type(), len)?);
+                }
+
+                let mut chunk = Chunk::try_new(arrays)?;
+                if let Some(p) = post_filter {
+                    chunk = filter_chunk(&chunk, &p.evaluate(&full_schema, chunk.arrays())?)?;
+                }
+                if !chunk.is_empty() {
+                    chunks.push(Ok(chunk));
+                }
+            }
+        }
+
+        let schema = schema.ok_or(FxError::EmptyContent)?;
+
+        FxChunkIter::<_, FxBatch>::new(schema, chunks.into_iter()).collect_into()
+    }
+}
+
+// ================================================================================================
+// Write helpers
+// ================================================================================================
+
+fn write_partitions<I, P>(
+    schema: Schema,
+    chunks: I,
+    root: P,
+    options: &DatasetWriteOptions,
+) -> FxResult<Vec<PathBuf>>
+where
+    I: IntoIterator<Item = Chunk<Box<dyn Array>>>,
+    P: AsRef<Path>,
+{
+    let part_idx = options
+        .partition_by
+        .iter()
+        .map(|n| {
+            schema
+                .fields
+                .iter()
+                .position(|f| &f.name == n)
+                .ok_or_else(|| FxError::InvalidArgument(format!("column `{n}` not found")))
+        })
+        .collect::<FxResult<Vec<_>>>()?;
+    let data_idx = (0..schema.fields.len())
+        .filter(|i| !part_idx.contains(i))
+        .collect::<Vec<_>>();
+    if data_idx.is_empty() {
+        return Err(FxError::InvalidArgument(
+            "at least one column should not be a partition column".to_string(),
+        ));
+    }
+    let data_schema = Schema::from(
+        data_idx
+            .iter()
+            .map(|&i| schema.fields[i].clone())
+            .collect::<Vec<_>>(),
+    );
+
+    // partitions in the order of their first rows
+    let mut keys: Vec<PartitionKey> = Vec::new();
+    let mut partitions: HashMap<PartitionKey, Vec<ChunkArr>> = HashMap::new();
+    for chunk in chunks {
+        for (key, indices) in group_rows(chunk.arrays(), &part_idx)? {
+            let indices = PrimitiveArray::<u32>::from_vec(indices);
+            let arrays = data_idx
+                .iter()
+                .map(|&i| take(chunk.arrays()[i].as_ref(), &indices).map(ArcArr::from))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if !partitions.contains_key(&key) {
+                keys.push(key.clone());
+            }
+            partitions
+                .entry(key)
+                .or_default()
+                .push(ChunkArr::try_new(arrays)?);
+        }
+    }
+
+    let mut res = Vec::new();
+    for key in keys {
+        let mut dir = root.as_ref().to_path_buf();
+        for (name, value) in options.partition_by.iter().zip(&key) {
+            let value = match value {
+                Some(v) => escape_path_value(v),
+                None => HIVE_DEFAULT_PARTITION.to_string(),
+            };
+            dir.push(format!("{}={value}", escape_path_value(name)));
+        }
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("part-{}.parquet", next_part_index(&dir)?));
+        let file = File::create(&path)?;
+        let chunks = partitions.remove(&key).unwrap_or_default();
+        FxIO::write_parquet_iter_with(
+            data_schema.clone(),
+            chunks,
+            file,
+            options.max_rows,
+            options.parquet.clone(),
+        )?;
+
+        res.push(path);
+    }
+
+    Ok(res)
+}
+
+// row indices of each partition, in the order of their first rows
+fn group_rows(
+    arrays: &[Box<dyn Array>],
+    part_idx: &[usize],
+) -> FxResult<Vec<(PartitionKey, Vec<u32>)>> {
+    let len = arrays.first().map_or(0, |a| a.len());
+    let columns = part_idx
+        .iter()
+        .map(|&i| {
+            let array = arrays[i].as_ref();
+            let display = get_display::<String>(array, "");
+            let is_float = matches!(
+                array.data_type(),
+                DataType::Float16 | DataType::Float32 | DataType::Float64
+            );
+            (0..len)
+                .map(|row| {
+                    if array.is_null(row) {
+                        return Ok(None);
+                    }
+                    let mut s = String::new();
+                    display(&mut s, row).map_err(|e| FxError::InvalidArgument(e.to_string()))?;
+                    // `1.0` displays as `1`, which would be read back as an integer
+                    if is_float && s.parse::<i64>().is_ok() {
+                        s.push_str(".0");
+                    }
+                    Ok(Some(s))
+                })
+                .collect::<FxResult<Vec<_>>>()
+        })
+        .collect::<FxResult<Vec<_>>>()?;
+
+    let mut res: Vec<(PartitionKey, Vec<u32>)> = Vec::new();
+    let mut positions: HashMap<PartitionKey, usize> = HashMap::new();
+    for row in 0..len {
+        let key = columns.iter().map(|c| c[row].clone()).collect::<Vec<_>>();
+        match positions.get(&key) {
+            Some(&p) => res[p].1.push(row as u32),
+            None => {
+                positions.insert(key.clone(), res.len());
+                res.push((key, vec![row as u32]));
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn next_part_index(dir: &Path) -> FxResult<usize> {
+    let mut next = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let idx = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("part-"))
+            .and_then(|n| n.strip_suffix(".parquet"))
+            .and_then(|n| n.parse::<usize>().ok());
+        if let Some(idx) = idx {
+            next = next.max(idx + 1);
+        }
+    }
+
+    Ok(next)
+}
+
+// ================================================================================================
+// Read helpers
+// ================================================================================================
+
+// hidden files & directories, e.g. `.crc` & `_SUCCESS`, are skipped
+fn discover_files(dir: &Path, files: &mut Vec<PathBuf>) -> FxResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_none_or(|n| n.starts_with('.') || n.starts_with('_'));
+        if hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            discover_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "parquet") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// `(name, value)` of each directory level, `None` for null values
+fn parse_partition(root: &Path, file: &Path) -> FxResult<Vec<(String, Option<String>)>> {
+    let dir = file
+        .parent()
+        .and_then(|p| p.strip_prefix(root).ok())
+        .ok_or_else(|| FxError::InvalidArgument(format!("invalid path `{}`", file.display())))?;
+
+    dir.components()
+        .map(|c| {
+            let c = c.as_os_str().to_string_lossy();
+            let (name, value) = c.split_once('=').ok_or_else(|| {
+                FxError::InvalidArgument(format!("invalid partition directory `{c}`"))
+            })?;
+            let value = (value != HIVE_DEFAULT_PARTITION).then(|| unescape_path_value(value));
+
+            Ok((unescape_path_value(name), value))
+        })
+        .collect()
+}
+
+fn infer_type<'a, I: IntoIterator<Item = &'a Option<String>>>(values: I) -> DataType {
+    let values = values.into_iter().flatten().collect::<Vec<_>>();
+
+    if !values.is_empty() && values.iter().all(|v| v.parse::<i64>().is_ok()) {
+        DataType::Int64
+    } else if !values.is_empty() && values.iter().all(|v| v.parse::<f64>().is_ok()) {
+        DataType::Float64
+    } else {
+        DataType::Utf8
+    }
+}
+
+// a single-valued array
+fn partition_value(value: Option<&str>, data_type: &DataType) -> FxResult<Option<Box<dyn Array>>> {
+    let value = match value {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    let array = Utf8Array::<i32>::from_slice([value]);
+    let array = match data_type {
+        DataType::Utf8 => array.boxed(),
+        _ => cast(&array, data_type, CastOptions::default())?,
+    };
+    if array.is_null(0) {
+        return Err(FxError::InvalidArgument(format!(
+            "partition value `{value}` is not a valid {data_type:?}"
+        )));
+    }
+
+    Ok(Some(array))
+}
+
+fn partition_stats<'a>(
+    fields: &'a [Field],
+    values: &[Option<Box<dyn Array>>],
+) -> FxResult<HashMap<&'a str, ColumnStats>> {
+    let mut res = HashMap::new();
+    for (field, value) in fields.iter().zip(values) {
+        let v = match value {
+            Some(a) => match array_values(a.as_ref())? {
+                Some(mut v) => v.remove(0),
+                // not comparable, assumed to match
+                None => continue,
+            },
+            None => FxValue::Null,
+        };
+        let stats = ColumnStats {
+            null_count: Some(v.is_null() as u64),
+            min: v.clone(),
+            max: v,
+            num_rows: 1,
+        };
+        res.insert(field.name.as_str(), stats);
+    }
+
+    Ok(res)
+}
+
+fn repeat_value(
+    value: Option<&dyn Array>,
+    data_type: &DataType,
+    len: usize,
+) -> FxResult<Box<dyn Array>> {
+    match value {
+        Some(v) => Ok(take(v, &PrimitiveArray::<u32>::from_vec(vec![0; len]))?),
+        None => Ok(new_null_array(data_type.clone(), len)),
+    }
+}
+
+fn read_file_schema(reader: &mut File) -> FxResult<Schema> {
+    let metadata = arrow2::io::parquet::read::read_metadata(reader)?;
+
+    Ok(arrow2::io::parquet::read::infer_schema(&metadata)?)
+}
+
+// ================================================================================================
+// Path escaping
+//
+// Same characters as Hive, escaped as `%XX`.
+// ================================================================================================
+
+fn needs_escape(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '"' | '#' | '%' | '\'' | '*' | '/' | ':' | '=' | '?' | '\\' | '{' | '[' | ']' | '^'
+        )
+}
+
+fn escape_path_value(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for c in value.chars() {
+        if needs_escape(c) {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                res.push_str(&format!("%{b:02X}"));
+            }
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
+
+fn unescape_path_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut res = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(b) => {
+                res.push(b);
+                i += 3;
+            }
+            None => {
+                res.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&res).into_owned()
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_dataset {
+
+    use std::sync::Arc;
+
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+
+    use super::*;
+
+    const DIR_DATASET: &str = "./cache/test_dataset";
+
+    fn rows(data: &FxBatches<FxBatch>) -> usize {
+        data.data.iter().filter_map(|b| b.max_len()).sum()
+    }
+
+    #[test]
+    fn dataset_write_read_success() {
+        let _ = std::fs::remove_dir_all(DIR_DATASET);
+
+        let date =
+            PrimitiveArray::<i32>::from_slice([19000, 19000, 19001, 19001]).to(DataType::Date32);
+        let data = FxBatch::new_with_names(
+            vec![
+                Arc::new(date),
+                arc_arr!([Some("A"), Some("B/C"), Some("A"), None]),
+                arc_arr!([1.0, 2.0, 3.0, 4.0]),
+            ],
+            ["date", "symbol", "price"],
+        );
+        let options = DatasetWriteOptions::new(["date", "symbol"]);
+
+        let files = FxIO::write_dataset(data.clone(), DIR_DATASET, &options).unwrap();
+        assert_eq!(files.len(), 4);
+        assert!(files[0].ends_with("date=2022-01-08/symbol=A/part-0.parquet"));
+        assert!(files[1].ends_with("date=2022-01-08/symbol=B%2FC/part-0.parquet"));
+        assert!(files[3].ends_with(format!(
+            "date=2022-01-09/symbol={HIVE_DEFAULT_PARTITION}/part-0.parquet"
+        )));
+
+        // partition columns are appended, and typed by the partition schema
+        let partition_schema = Schema::from(vec![Field::new("date", DataType::Date32, true)]);
+        let options = DatasetReadOptions::default().with_partition_schema(partition_schema);
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(res.names(), vec!["price", "date", "symbol"]);
+        assert_eq!(res.schema().fields[1].data_type(), &DataType::Date32);
+        assert_eq!(rows(&res), 4);
+
+        // inferred partition types
+        let res = FxIO::read_dataset(DIR_DATASET, DatasetReadOptions::default()).unwrap();
+        assert_eq!(res.schema().fields[1].data_type(), &DataType::Utf8);
+
+        // pruned by partition values, then filtered
+        let predicate = FxPredicate::eq("symbol", "A").and(FxPredicate::gt("price", 1.0));
+        let options = DatasetReadOptions::default().with_predicate(predicate);
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(rows(&res), 1);
+
+        let options = DatasetReadOptions::default().with_predicate(FxPredicate::is_null("symbol"));
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(rows(&res), 1);
+
+        // typed date partitions, compared by parsed literals or days since epoch
+        let partition_schema = Schema::from(vec![Field::new("date", DataType::Date32, true)]);
+        let options = DatasetReadOptions::default()
+            .with_partition_schema(partition_schema.clone())
+            .with_predicate(FxPredicate::eq("date", "2022-01-09"));
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(rows(&res), 2);
+        let predicate = FxPredicate::lt("date", 19001).and(FxPredicate::gt("price", 1.0));
+        let options = DatasetReadOptions::default()
+            .with_partition_schema(partition_schema.clone())
+            .with_predicate(predicate);
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(rows(&res), 1);
+        let options = DatasetReadOptions::default()
+            .with_partition_schema(partition_schema)
+            .with_predicate(FxPredicate::eq("date", "not a date"));
+        assert!(FxIO::read_dataset(DIR_DATASET, options).is_err());
+
+        // pushed down to the files
+        let options = DatasetReadOptions::default().with_predicate(FxPredicate::gt("price", 2.5));
+        let res = FxIO::read_dataset(DIR_DATASET, options).unwrap();
+        assert_eq!(rows(&res), 2);
+
+        // new files are appended
+        let options = DatasetWriteOptions::new(["date", "symbol"]);
+        let files = FxIO::write_dataset(data, DIR_DATASET, &options).unwrap();
+        assert!(files[0].ends_with("part-1.parquet"));
+        let res = FxIO::read_dataset(DIR_DATASET, DatasetReadOptions::default()).unwrap();
+        assert_eq!(rows(&res), 8);
+    }
+
+    #[test]
+    fn dataset_write_tabular_success() {
+        use crate::ab::Dqs;
+        use crate::cont::FxTabular;
+
+        const DIR: &str = "./cache/test_dataset_tabular";
+        let _ = std::fs::remove_dir_all(DIR);
+
+        let mut data =
+            FxTabular::new_with_names(vec![arc_arr!([1, 2]), arc_arr!(["x", "y"])], ["k", "v"]);
+        data.push_back(vec![arc_arr!([2, 3]), arc_arr!(["z", "w"])])
+            .unwrap();
+
+        let options = DatasetWriteOptions::new(["k"]).with_max_rows(1);
+        let files = FxIO::write_dataset_from(data, DIR, &options).unwrap();
+        assert_eq!(files.len(), 3);
+
+        let res = FxIO::read_dataset(DIR, DatasetReadOptions::default()).unwrap();
+        assert_eq!(res.names(), vec!["v", "k"]);
+        assert_eq!(res.schema().fields[1].data_type(), &DataType::Int64);
+        assert_eq!(rows(&res), 4);
+
+        // integral floats are read back as floats
+        const DIR_FLOAT: &str = "./cache/test_dataset_float";
+        let _ = std::fs::remove_dir_all(DIR_FLOAT);
+        let data =
+            FxBatch::new_with_names(vec![arc_arr!([1.0, 2.0]), arc_arr!(["x", "y"])], ["k", "v"]);
+        let files = FxIO::write_dataset(data, DIR_FLOAT, &DatasetWriteOptions::new(["k"])).unwrap();
+        assert!(files[0].ends_with("k=1.0/part-0.parquet"));
+        let res = FxIO::read_dataset(DIR_FLOAT, DatasetReadOptions::default()).unwrap();
+        assert_eq!(res.schema().fields[1].data_type(), &DataType::Float64);
+
+        // partition columns only
+        let options = DatasetWriteOptions::new(["k", "v"]);
+        let data = FxBatch::new_with_names(vec![arc_arr!([1]), arc_arr!(["x"])], ["k", "v"]);
+        assert!(FxIO::write_dataset(data, DIR, &options).is_err());
+    }
+
+    #[test]
+    fn path_escape_success() {
+        let s = "a/b=c%d:e";
+        let escaped = escape_path_value(s);
+        assert_eq!(escaped, "a%2Fb%3Dc%25d%3Ae");
+        assert_eq!(unescape_path_value(&escaped), s);
+        assert_eq!(unescape_path_value("100%"), "100%");
+    }
+}
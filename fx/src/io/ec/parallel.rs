@@ -3,7 +3,10 @@
 //! date: 2023/04/01 19:30:31 Saturday
 //! brief:
 
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::num::NonZeroUsize;
+use std::sync::{mpsc as std_mpsc, Mutex};
 // use std::sync::Arc;
 
 // use futures::io::AsyncWrite;
@@ -11,13 +14,14 @@ use std::io::Write;
 // use super::{AsyncReadSeek};
 use super::ReadSeek;
 use crate::cont::FxTabular;
-use crate::error::FxResult;
+use crate::error::{FxError, FxResult};
 
 #[derive(Default)]
 pub struct ParallelIO {
     pub(crate) data: Option<FxTabular>,
     pub(crate) writer: Option<Box<dyn Write>>,
     pub(crate) reader: Option<Box<dyn ReadSeek>>,
+    pub(crate) parallel_num: Option<usize>,
     // pub(crate) async_writer: Option<Arc<dyn AsyncWrite>>,
     // pub(crate) async_reader: Option<Arc<dyn AsyncReadSeek>>,
 }
@@ -42,6 +46,26 @@ impl ParallelIO {
         self.data.take()
     }
 
+    /// Number of worker threads, defaults to the available parallelism.
+    pub fn set_parallel_num(&mut self, parallel_num: usize) -> FxResult<()> {
+        if parallel_num == 0 {
+            return Err(FxError::InvalidArgument(
+                "parallel num should be greater than 0".to_string(),
+            ));
+        }
+
+        self.parallel_num = Some(parallel_num);
+        Ok(())
+    }
+
+    pub fn parallel_num(&self) -> usize {
+        self.parallel_num.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1)
+        })
+    }
+
     pub fn set_file_writer(&mut self, path: &str) -> FxResult<()> {
         self.writer = Some(Box::new(std::fs::File::create(path)?));
         Ok(())
@@ -52,3 +76,119 @@ impl ParallelIO {
         Ok(())
     }
 }
+
+// ================================================================================================
+// Ordered worker pool
+//
+// `items` are mapped by `f` on `workers` threads, and the outputs are fed to `sink` in the order
+// of `items`. An item is taken only with a permit, which is given back once its output is sunk,
+// hence at most `workers` items are in flight, and so are the outputs waiting to be reordered.
+// The first error stops the pool.
+// ================================================================================================
+
+pub(crate) fn par_map_ordered<T, R, F, S>(
+    items: Vec<T>,
+    workers: usize,
+    f: F,
+    mut sink: S,
+) -> FxResult<()>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> FxResult<R> + Sync,
+    S: FnMut(R) -> FxResult<()>,
+{
+    let len = items.len();
+    let workers = workers.clamp(1, len.max(1));
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (permit_tx, permit_rx) = std_mpsc::sync_channel::<()>(workers);
+    for _ in 0..workers {
+        permit_tx.send(()).unwrap();
+    }
+    let permits = Mutex::new(permit_rx);
+
+    std::thread::scope(|scope| {
+        // moved in, so that waiting workers are released once the pool stops
+        let permit_tx = permit_tx;
+        let (tx, rx) = std_mpsc::sync_channel::<(usize, FxResult<R>)>(workers);
+
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (queue, permits, f) = (&queue, &permits, &f);
+            scope.spawn(move || loop {
+                // the sender is gone if the pool has been stopped
+                if permits.lock().unwrap().recv().is_err() {
+                    break;
+                }
+                let item = queue.lock().unwrap().next();
+                let Some((idx, item)) = item else {
+                    break;
+                };
+                // the receiver is gone if the pool has been stopped
+                if tx.send((idx, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (idx, res) in rx.iter() {
+            pending.insert(idx, res?);
+            while let Some(r) = pending.remove(&next) {
+                sink(r)?;
+                next += 1;
+                // workers may have exited already, once the queue is drained
+                let _ = permit_tx.try_send(());
+            }
+        }
+
+        Ok(())
+    })
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_parallel {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn par_map_ordered_success() {
+        let (taken, sunk) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let mut res = Vec::new();
+        par_map_ordered(
+            (0..100).collect(),
+            4,
+            |i: usize| {
+                // items are never taken ahead of the sunk ones by more than `workers`
+                let t = taken.fetch_add(1, Ordering::SeqCst);
+                assert!(t < sunk.load(Ordering::SeqCst) + 4);
+                Ok(i * 2)
+            },
+            |r| {
+                sunk.fetch_add(1, Ordering::SeqCst);
+                res.push(r);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(res, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+
+        let res = par_map_ordered(
+            (0..100).collect(),
+            4,
+            |i: usize| match i {
+                10 => Err(FxError::InvalidArgument("stop".to_string())),
+                _ => Ok(i),
+            },
+            |_| Ok(()),
+        );
+        assert!(res.is_err());
+    }
+}
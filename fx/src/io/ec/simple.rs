@@ -3,11 +3,14 @@
 //! date: 2023/03/15 23:20:05 Wednesday
 //! brief:
 
-use std::io::Write;
+use std::cell::RefCell;
+use std::io::{Cursor, Write};
+use std::rc::Rc;
 
 use super::ReadSeek;
 use crate::ab::{Eclectic, Purport};
-use crate::error::FxResult;
+use crate::error::{FxError, FxResult};
+use crate::io::{DecompressReader, FxCompression};
 
 pub struct SimpleIO<T>
 where
@@ -16,6 +19,10 @@ where
     pub(crate) data: Option<T>,
     pub(crate) writer: Option<Box<dyn Write>>,
     pub(crate) reader: Option<Box<dyn ReadSeek>>,
+    // shared with the in-memory writer, which is taken by writing
+    pub(crate) buffer: Option<SharedCursor>,
+    // compression of the writer, the stream is finished after writing
+    pub(crate) compression: Option<FxCompression>,
 }
 
 impl<T> Default for SimpleIO<T>
@@ -27,6 +34,8 @@ where
             data: Default::default(),
             writer: Default::default(),
             reader: Default::default(),
+            buffer: Default::default(),
+            compression: Default::default(),
         }
     }
 }
@@ -54,21 +63,201 @@ where
         self.data.take()
     }
 
+    /// Files ending with `.gz` or `.zst` are compressed, see `set_file_writer_with`.
     pub fn set_file_writer(&mut self, path: &str) -> FxResult<()> {
-        self.writer = Some(Box::new(std::fs::File::create(path)?));
+        self.set_file_writer_with(path, FxCompression::from_path(path))
+    }
+
+    /// The compressed stream is finished by writing, errors of which are returned.
+    pub fn set_file_writer_with(
+        &mut self,
+        path: &str,
+        compression: Option<FxCompression>,
+    ) -> FxResult<()> {
+        let file = std::fs::File::create(path)?;
+        self.set_writer(file);
+        self.compression = compression;
         Ok(())
     }
 
+    /// Gzip or zstd compressed files are decoded while reading, see `DecompressReader`.
     pub fn set_file_reader(&mut self, path: &str) -> FxResult<()> {
-        self.reader = Some(Box::new(std::fs::File::open(path)?));
+        let file = std::fs::File::open(path)?;
+        self.reader = Some(Box::new(DecompressReader::try_new(file)?));
         Ok(())
     }
 
-    // pub fn set_buf_writer(&mut self) {}
+    pub fn set_writer<W: Write + 'static>(&mut self, writer: W) {
+        self.writer = Some(Box::new(writer));
+        self.buffer = None;
+        self.compression = None;
+    }
+
+    pub fn set_reader<R: ReadSeek + 'static>(&mut self, reader: R) {
+        self.reader = Some(Box::new(reader));
+    }
+
+    /// Writes into memory, the bytes are taken by `take_written_bytes`.
+    pub fn set_buf_writer(&mut self) {
+        self.set_cursor_writer(Cursor::new(Vec::new()));
+    }
+
+    pub fn set_buf_reader(&mut self, bytes: Vec<u8>) {
+        self.set_cursor_reader(Cursor::new(bytes));
+    }
+
+    /// Writes into memory from the cursor's position, the whole buffer is taken by
+    /// `take_written_bytes`.
+    pub fn set_cursor_writer(&mut self, cursor: Cursor<Vec<u8>>) {
+        let buffer = SharedCursor(Rc::new(RefCell::new(cursor)));
+        self.writer = Some(Box::new(buffer.clone()));
+        self.buffer = Some(buffer);
+        self.compression = None;
+    }
+
+    pub fn set_cursor_reader<B: AsRef<[u8]> + 'static>(&mut self, cursor: Cursor<B>) {
+        self.set_reader(cursor);
+    }
+
+    /// Takes the bytes written by the in-memory writer, `None` if it is not set.
+    pub fn take_written_bytes(&mut self) -> Option<Vec<u8>> {
+        let buffer = self.buffer.as_ref()?;
+        let cursor = std::mem::take(&mut *buffer.0.borrow_mut());
+
+        Some(cursor.into_inner())
+    }
+
+    // takes the data & the writer, notice after writing complete, both turn to None
+    pub(crate) fn write_data<F>(&mut self, f: F) -> FxResult<()>
+    where
+        F: FnOnce(T, &mut dyn Write) -> FxResult<()>,
+    {
+        if self.data.is_none() || self.writer.is_none() {
+            return Err(FxError::EmptyContent);
+        }
 
-    // pub fn set_buf_reader(&mut self) {}
+        let mut writer = self.writer.take().unwrap();
+        let data = self.take_data().unwrap();
+
+        match self.compression.take() {
+            Some(c) => c.encode_with(writer, |w| f(data, w))?.flush()?,
+            None => f(data, &mut writer)?,
+        }
+
+        Ok(())
+    }
+}
 
-    // pub fn set_cursor_writer(&mut self) {}
+// ================================================================================================
+// SharedCursor
+// ================================================================================================
 
-    // pub fn set_cursor_reader(&mut self) {}
+#[derive(Clone)]
+pub(crate) struct SharedCursor(Rc<RefCell<Cursor<Vec<u8>>>>);
+
+impl Write for SharedCursor {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_simple {
+    use super::*;
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+    use crate::cont::FxBatch;
+
+    #[test]
+    fn buf_round_trip_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+        let mut simple = SimpleIO::new_with_data(data.clone());
+        assert!(simple.take_written_bytes().is_none());
+
+        simple.set_buf_writer();
+        simple.write_parquet(None).unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        assert!(bytes.starts_with(b"PAR1"));
+
+        // taken
+        assert_eq!(simple.take_written_bytes(), Some(vec![]));
+
+        simple.set_buf_reader(bytes);
+        simple.read_parquet().unwrap();
+        assert_eq!(simple.data().unwrap().names(), vec!["c1", "c2"]);
+
+        // every format
+        let mut simple = SimpleIO::new_with_data(data);
+        simple.set_buf_writer();
+        simple.write_ipc(None).unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        simple.set_buf_reader(bytes);
+        simple.read_ipc().unwrap();
+
+        simple.set_buf_writer();
+        simple.write_arvo(None).unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        simple.set_buf_reader(bytes);
+        simple.read_arvo().unwrap();
+
+        simple.set_buf_writer();
+        simple.write_csv(None).unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        simple.set_buf_reader(bytes);
+        simple.read_csv(None).unwrap();
+
+        simple.set_buf_writer();
+        simple.write_ndjson().unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        simple.set_buf_reader(bytes);
+        simple.read_ndjson().unwrap();
+        assert_eq!(simple.data().unwrap().max_len(), Some(3));
+    }
+
+    #[test]
+    fn cursor_and_generic_success() {
+        // appended after the existing bytes
+        let mut cursor = Cursor::new(b"c0\n".to_vec());
+        cursor.set_position(3);
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+        let mut simple = SimpleIO::new_with_data(data);
+        simple.set_cursor_writer(cursor);
+        simple.write_json().unwrap();
+        let bytes = simple.take_written_bytes().unwrap();
+        assert!(bytes.starts_with(b"c0\n["));
+
+        simple.set_cursor_reader(Cursor::new(bytes[3..].to_vec()));
+        simple.read_json().unwrap();
+        assert_eq!(simple.data().unwrap().max_len(), Some(3));
+
+        // a generic writer replaces the in-memory one
+        simple.set_writer(Vec::new());
+        assert!(simple.take_written_bytes().is_none());
+        simple.write_csv(None).unwrap();
+
+        simple.set_reader(Cursor::new("c1,c2\n1,a\n"));
+        simple.read_csv(None).unwrap();
+        assert_eq!(simple.data().unwrap().max_len(), Some(1));
+    }
 }
@@ -0,0 +1,521 @@
+//! file: excel.rs
+//! author: Jacob Xie
+//! date: 2026/10/19 03:05:26 Monday
+//! brief: Excel (xlsx/xls/xlsb) & ODS worksheet import
+
+use std::path::Path;
+
+use arrow2::array::{Array, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::temporal_conversions::{date32_to_date, timestamp_ms_to_datetime};
+use calamine::{open_workbook_auto, Data, DataType as _, Reader};
+
+use super::FxIO;
+use crate::ab::{Eclectic, FxChunkIter};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// ExcelReadOptions
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct ExcelReadOptions {
+    /// Name of the worksheet, the first one if `None`
+    pub sheet: Option<String>,
+    /// Cells in A1 notation, e.g. `B2:E100`, all the used cells from `A1` if `None`
+    pub range: Option<String>,
+    /// 0-based offset of the header within the rows of `range`, rows above it are skipped.
+    /// Columns are named `column_1`, `column_2`... if `None`
+    pub header_row: Option<usize>,
+    /// Types of the columns picked by name, inferred from the cells if `None`
+    pub schema: Option<Schema>,
+}
+
+impl ExcelReadOptions {
+    pub fn with_sheet<S: Into<String>>(mut self, sheet: S) -> Self {
+        self.sheet = Some(sheet.into());
+        self
+    }
+
+    pub fn with_range<S: Into<String>>(mut self, range: S) -> Self {
+        self.range = Some(range.into());
+        self
+    }
+
+    pub fn with_header_row(mut self, header_row: usize) -> Self {
+        self.header_row = Some(header_row);
+        self
+    }
+
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+}
+
+// ================================================================================================
+// A1 notation
+// ================================================================================================
+
+/// Parses a cell, e.g. `AB12`, into 0-based (row, column).
+fn parse_cell(cell: &str) -> Option<(u32, u32)> {
+    let idx = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(idx);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let col = letters.chars().try_fold(0u32, |acc, c| {
+        let d = c.to_ascii_uppercase() as u32 - 'A' as u32 + 1;
+        acc.checked_mul(26)?.checked_add(d)
+    })?;
+    let row = digits.parse::<u32>().ok()?;
+    if row == 0 {
+        return None;
+    }
+
+    Some((row - 1, col - 1))
+}
+
+/// Formats 0-based (row, column) into a cell, e.g. `AB12`.
+fn format_cell(row: u32, col: u32) -> String {
+    let mut letters = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        letters.push((b'A' + ((n - 1) % 26) as u8) as char);
+        n = (n - 1) / 26;
+    }
+
+    letters.iter().rev().collect::<String>() + &(row + 1).to_string()
+}
+
+/// Parses a range, e.g. `B2:E100`, into 0-based (start, end), both inclusive.
+fn parse_range(range: &str) -> FxResult<((u32, u32), (u32, u32))> {
+    let err = || FxError::InvalidArgument(format!("invalid range `{range}`"));
+
+    let (start, end) = range.split_once(':').ok_or_else(err)?;
+    let start = parse_cell(start.trim()).ok_or_else(err)?;
+    let end = parse_cell(end.trim()).ok_or_else(err)?;
+    if start.0 > end.0 || start.1 > end.1 {
+        return Err(err());
+    }
+
+    Ok((start, end))
+}
+
+// ================================================================================================
+// Cells -> Array
+// ================================================================================================
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Date(i32),
+    Datetime(i64),
+    Str(String),
+}
+
+impl From<&Data> for Cell {
+    fn from(data: &Data) -> Self {
+        match data {
+            Data::Empty | Data::Error(_) => Cell::Null,
+            Data::Bool(v) => Cell::Bool(*v),
+            Data::Int(v) => Cell::Int(*v),
+            // spreadsheets store all numbers as floats
+            Data::Float(v) if v.fract() == 0.0 && v.abs() < i64::MAX as f64 => Cell::Int(*v as i64),
+            Data::Float(v) => Cell::Float(*v),
+            Data::String(v) if v.trim().is_empty() => Cell::Null,
+            Data::String(v) => Cell::Str(v.clone()),
+            Data::DateTime(v) if v.is_duration() => Cell::Str(data.to_string()),
+            Data::DateTime(_) | Data::DateTimeIso(_) => {
+                match data.as_datetime().map(|dt| dt.timestamp_millis()) {
+                    Some(ms) if ms % MS_PER_DAY == 0 => Cell::Date((ms / MS_PER_DAY) as i32),
+                    Some(ms) => Cell::Datetime(ms),
+                    None => match data.as_date() {
+                        Some(d) => Cell::Date(
+                            (d.and_hms_opt(0, 0, 0).unwrap().timestamp_millis() / MS_PER_DAY)
+                                as i32,
+                        ),
+                        None => Cell::Str(data.to_string()),
+                    },
+                }
+            }
+            Data::DurationIso(v) => Cell::Str(v.clone()),
+        }
+    }
+}
+
+impl Cell {
+    fn to_str(&self) -> Option<String> {
+        match self {
+            Cell::Null => None,
+            Cell::Bool(v) => Some(v.to_string()),
+            Cell::Int(v) => Some(v.to_string()),
+            Cell::Float(v) => Some(v.to_string()),
+            Cell::Date(v) => Some(date32_to_date(*v).to_string()),
+            Cell::Datetime(v) => Some(timestamp_ms_to_datetime(*v).to_string()),
+            Cell::Str(v) => Some(v.clone()),
+        }
+    }
+}
+
+/// Infers the type of a column: booleans, integers, floats (integers are widened), dates,
+/// timestamps (dates are widened) and strings for anything else.
+fn infer_type(cells: &[Cell]) -> DataType {
+    let mut res: Option<DataType> = None;
+
+    for c in cells {
+        let dt = match c {
+            Cell::Null => continue,
+            Cell::Bool(_) => DataType::Boolean,
+            Cell::Int(_) => DataType::Int64,
+            Cell::Float(_) => DataType::Float64,
+            Cell::Date(_) => DataType::Date32,
+            Cell::Datetime(_) => DataType::Timestamp(TimeUnit::Millisecond, None),
+            Cell::Str(_) => return DataType::Utf8,
+        };
+
+        res = Some(match (res, dt) {
+            (None, dt) => dt,
+            (Some(a), b) if a == b => a,
+            (Some(DataType::Int64), DataType::Float64)
+            | (Some(DataType::Float64), DataType::Int64) => DataType::Float64,
+            (Some(DataType::Date32), b @ DataType::Timestamp(_, _))
+            | (Some(b @ DataType::Timestamp(_, _)), DataType::Date32) => b,
+            _ => return DataType::Utf8,
+        });
+    }
+
+    res.unwrap_or(DataType::Utf8)
+}
+
+fn cells_to_array(cells: &[Cell], data_type: &DataType) -> Box<dyn Array> {
+    match data_type {
+        DataType::Boolean => cells
+            .iter()
+            .map(|c| match c {
+                Cell::Bool(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<BooleanArray>()
+            .boxed(),
+        DataType::Int64 => cells
+            .iter()
+            .map(|c| match c {
+                Cell::Int(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<PrimitiveArray<i64>>()
+            .boxed(),
+        DataType::Float64 => cells
+            .iter()
+            .map(|c| match c {
+                Cell::Int(v) => Some(*v as f64),
+                Cell::Float(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<PrimitiveArray<f64>>()
+            .boxed(),
+        DataType::Date32 => cells
+            .iter()
+            .map(|c| match c {
+                Cell::Date(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<PrimitiveArray<i32>>()
+            .to(DataType::Date32)
+            .boxed(),
+        DataType::Timestamp(_, _) => cells
+            .iter()
+            .map(|c| match c {
+                Cell::Date(v) => Some(*v as i64 * MS_PER_DAY),
+                Cell::Datetime(v) => Some(*v),
+                _ => None,
+            })
+            .collect::<PrimitiveArray<i64>>()
+            .to(data_type.clone())
+            .boxed(),
+        _ => cells
+            .iter()
+            .map(Cell::to_str)
+            .collect::<Utf8Array<i32>>()
+            .boxed(),
+    }
+}
+
+// ================================================================================================
+// Excel
+// ================================================================================================
+
+impl FxIO {
+    /// Reads a worksheet of a xlsx, xlsm, xls, xlsb or ods file, detected by its extension.
+    ///
+    /// See [`ExcelReadOptions`] for `sheet`, `range` & `header_row`.
+    pub fn read_excel<D: Eclectic, P: AsRef<Path>>(
+        path: P,
+        sheet: Option<&str>,
+        range: Option<&str>,
+        header_row: Option<usize>,
+    ) -> FxResult<D> {
+        let options = ExcelReadOptions {
+            sheet: sheet.map(str::to_string),
+            range: range.map(str::to_string),
+            header_row,
+            schema: None,
+        };
+
+        Self::read_excel_with(path, options)
+    }
+
+    /// Reads a worksheet, with the types of the columns overridden by `options.schema`.
+    pub fn read_excel_with<D: Eclectic, P: AsRef<Path>>(
+        path: P,
+        options: ExcelReadOptions,
+    ) -> FxResult<D> {
+        let mut workbook = open_workbook_auto(path)?;
+
+        let sheets = workbook.sheet_names();
+        let sheet = match &options.sheet {
+            Some(s) if sheets.contains(s) => s.clone(),
+            Some(s) => return Err(FxError::InvalidArgument(format!("sheet `{s}` not found"))),
+            None => sheets.first().cloned().ok_or(FxError::EmptyContent)?,
+        };
+        let used = workbook.worksheet_range(&sheet)?;
+
+        let area = match &options.range {
+            Some(r) => {
+                let (start, end) = parse_range(r)?;
+                used.range(start, end)
+            }
+            None => match used.end() {
+                Some(end) => used.range((0, 0), end),
+                None => return Err(FxError::EmptyContent),
+            },
+        };
+        let width = area.width();
+        // 0-based (row, column) of the first data cell in the sheet
+        let (first_row, first_col) = area.start().unwrap_or((0, 0));
+        let first_row = first_row + options.header_row.map_or(0, |h| h as u32 + 1);
+
+        let mut rows = area.rows().skip(options.header_row.unwrap_or(0));
+        let names = match options.header_row {
+            Some(_) => {
+                let header = rows.next().ok_or(FxError::EmptyContent)?;
+                header
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| match c.as_string() {
+                        Some(s) if !s.trim().is_empty() => s.trim().to_string(),
+                        _ => format!("column_{}", i + 1),
+                    })
+                    .collect::<Vec<_>>()
+            }
+            None => (1..=width).map(|i| format!("column_{i}")).collect(),
+        };
+
+        let mut columns = vec![Vec::new(); width];
+        for row in rows {
+            for (col, c) in columns.iter_mut().zip(row) {
+                col.push(Cell::from(c));
+            }
+        }
+
+        let (fields, arrays): (Vec<_>, Vec<_>) = match &options.schema {
+            Some(schema) => schema
+                .fields
+                .iter()
+                .map(|f| {
+                    let idx = names.iter().position(|n| n == &f.name).ok_or_else(|| {
+                        FxError::InvalidArgument(format!("column `{}` not found", f.name))
+                    })?;
+                    let cells = &columns[idx];
+                    let array = cells_to_array(cells, &infer_type(cells));
+                    if array.data_type() == f.data_type() {
+                        return Ok((f.clone(), array));
+                    }
+
+                    let res = cast(array.as_ref(), f.data_type(), CastOptions::default())?;
+                    // a cell failed to cast becomes null
+                    if res.null_count() != array.null_count() {
+                        let row = (0..array.len())
+                            .find(|&i| array.is_valid(i) && res.is_null(i))
+                            .unwrap_or_default();
+                        return Err(FxError::InvalidArgument(format!(
+                            "sheet `{sheet}` cell {}, column `{}`: cannot cast `{}` as {:?}",
+                            format_cell(first_row + row as u32, first_col + idx as u32),
+                            f.name,
+                            cells[row].to_str().unwrap_or_default(),
+                            f.data_type()
+                        )));
+                    }
+
+                    Ok((f.clone(), res))
+                })
+                .collect::<FxResult<Vec<_>>>()?
+                .into_iter()
+                .unzip(),
+            None => names
+                .into_iter()
+                .zip(&columns)
+                .map(|(n, cells)| {
+                    let data_type = infer_type(cells);
+                    let array = cells_to_array(cells, &data_type);
+
+                    (Field::new(n, data_type, true), array)
+                })
+                .unzip(),
+        };
+
+        let schema = Schema::from(fields);
+        let chunk = Chunk::try_new(arrays)?;
+
+        FxChunkIter::new(schema, std::iter::once(FxResult::Ok(chunk))).concat()
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_excel {
+    use super::*;
+    use crate::ab::{FromSlice, Purport};
+    use crate::arc_arr;
+    use crate::cont::{ArcArr, FxBatch};
+
+    const XLSX: &str = "./tests/data/people.xlsx";
+    const ODS: &str = "./tests/data/people.ods";
+
+    #[test]
+    fn parse_range_success() {
+        assert_eq!(parse_cell("A1"), Some((0, 0)));
+        assert_eq!(parse_cell("ab12"), Some((11, 27)));
+        assert_eq!(parse_cell("A0"), None);
+        assert_eq!(parse_cell("12"), None);
+        assert_eq!(parse_range("B2:E100").unwrap(), ((1, 1), (99, 4)));
+        assert!(parse_range("E2:B100").is_err());
+        assert!(parse_range("B2").is_err());
+        assert_eq!(format_cell(0, 0), "A1");
+        assert_eq!(format_cell(11, 27), "AB12");
+        assert_eq!(format_cell(2, 25), "Z3");
+    }
+
+    #[test]
+    fn read_excel_infer_success() {
+        for path in [XLSX, ODS] {
+            let res = FxIO::read_excel::<FxBatch, _>(path, Some("people"), None, Some(1)).unwrap();
+
+            assert_eq!(
+                res.names(),
+                vec!["id", "name", "score", "active", "joined", "updated"]
+            );
+            let types = res
+                .schema()
+                .fields
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                types,
+                vec![
+                    DataType::Int64,
+                    DataType::Utf8,
+                    DataType::Float64,
+                    DataType::Boolean,
+                    DataType::Date32,
+                    DataType::Timestamp(TimeUnit::Millisecond, None),
+                ]
+            );
+
+            let data = res.data();
+            assert_eq!(data[0], arc_arr!([Some(1i64), Some(2), Some(3)]));
+            assert_eq!(
+                data[1],
+                arc_arr!([Some("alice"), Some("bob"), Some("carol")])
+            );
+            assert_eq!(data[2], arc_arr!([Some(1.5f64), None, Some(3.25)]));
+            assert_eq!(data[3], arc_arr!([Some(true), Some(false), Some(true)]));
+            // 2023-01-02 & 2023-02-03
+            let joined = PrimitiveArray::from([Some(19359i32), Some(19391), None])
+                .to(DataType::Date32)
+                .boxed();
+            assert_eq!(data[4], ArcArr::from(joined));
+            // 2023-01-02T12:00:00 & 2023-02-03T06:00:00
+            let updated = PrimitiveArray::from([Some(1672660800000i64), Some(1675404000000), None])
+                .to(DataType::Timestamp(TimeUnit::Millisecond, None))
+                .boxed();
+            assert_eq!(data[5], ArcArr::from(updated));
+        }
+    }
+
+    #[test]
+    fn read_excel_range_success() {
+        for path in [XLSX, ODS] {
+            let res = FxIO::read_excel::<FxBatch, _>(path, Some("people"), Some("A2:C4"), Some(0))
+                .unwrap();
+            assert_eq!(res.names(), vec!["id", "name", "score"]);
+            assert_eq!(res.max_len(), Some(2));
+
+            // no header, mixed types become strings
+            let res = FxIO::read_excel::<FxBatch, _>(path, Some("misc"), None, None).unwrap();
+            assert_eq!(res.names(), vec!["column_1", "column_2"]);
+            assert_eq!(
+                res.data()[0],
+                arc_arr!([Some("1"), Some("x"), Some("true")])
+            );
+            assert_eq!(res.data()[1], arc_arr!([Some(10i64), Some(20), Some(30)]));
+        }
+    }
+
+    #[test]
+    fn read_excel_schema_success() {
+        let schema = Schema::from(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("id", DataType::Int32, true),
+            Field::new("score", DataType::Float32, true),
+        ]);
+        let options = ExcelReadOptions::default()
+            .with_sheet("people")
+            .with_header_row(1)
+            .with_schema(schema.clone());
+
+        let res = FxIO::read_excel_with::<FxBatch, _>(XLSX, options).unwrap();
+        assert_eq!(res.schema(), &schema);
+        assert_eq!(res.data()[1], arc_arr!([Some(1i32), Some(2), Some(3)]));
+        assert_eq!(res.data()[2], arc_arr!([Some(1.5f32), None, Some(3.25)]));
+    }
+
+    #[test]
+    fn read_excel_fail() {
+        let res = FxIO::read_excel::<FxBatch, _>(XLSX, Some("nope"), None, None);
+        assert_eq!(res.unwrap_err().to_string(), "sheet `nope` not found");
+
+        let options = ExcelReadOptions::default()
+            .with_header_row(1)
+            .with_schema(Schema::from(vec![Field::new("x", DataType::Utf8, true)]));
+        let res = FxIO::read_excel_with::<FxBatch, _>(ODS, options);
+        assert_eq!(res.unwrap_err().to_string(), "column `x` not found");
+
+        // cells failed to cast are reported rather than turned into nulls
+        let schema = Schema::from(vec![Field::new("name", DataType::Int32, true)]);
+        let options = ExcelReadOptions::default()
+            .with_sheet("people")
+            .with_header_row(1)
+            .with_schema(schema);
+        for path in [XLSX, ODS] {
+            let res = FxIO::read_excel_with::<FxBatch, _>(path, options.clone());
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "sheet `people` cell B3, column `name`: cannot cast `alice` as Int32"
+            );
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! file: ffi.rs
+//! author: Jacob Xie
+//! date: 2026/10/19 03:48:12 Monday
+//! brief: Arrow C Data & C Stream interface
+
+use std::sync::Arc;
+
+use arrow2::array::{Array, StructArray};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::ffi;
+
+use super::ab::{FromChunks, IntoChunks};
+use super::FxIO;
+use crate::ab::{Eclectic, FxChunkIter, FxSeq, Purport};
+use crate::cont::{ArcArr, ChunkArr};
+use crate::error::{FxError, FxResult};
+
+pub use arrow2::ffi::{ArrowArray, ArrowArrayStream, ArrowSchema};
+
+// ================================================================================================
+// Schema <-> Field
+//
+// A chunk crosses the boundary as a `StructArray`, the same as a record batch in pyarrow or
+// Arrow C++. The schema's metadata is carried by the struct field.
+// ================================================================================================
+
+fn schema_to_field(schema: &Schema) -> Field {
+    Field::new("", DataType::Struct(schema.fields.clone()), false)
+        .with_metadata(schema.metadata.clone())
+}
+
+fn field_to_schema(field: Field) -> FxResult<Schema> {
+    match field.data_type {
+        DataType::Struct(fields) => Ok(Schema::from(fields).with_metadata(field.metadata)),
+        dt => Err(FxError::InvalidType(format!(
+            "expected a struct array, got {dt:?}"
+        ))),
+    }
+}
+
+fn arrays_to_struct(schema: &Schema, arrays: Vec<Box<dyn Array>>) -> FxResult<Box<dyn Array>> {
+    let datatype = DataType::Struct(schema.fields.clone());
+
+    Ok(StructArray::try_new(datatype, arrays, None)?.boxed())
+}
+
+fn struct_to_arrays(array: Box<dyn Array>) -> FxResult<Vec<Box<dyn Array>>> {
+    let array = array
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .ok_or_else(|| FxError::InvalidType("expected a struct array".to_string()))?;
+
+    Ok(array.values().to_vec())
+}
+
+// ================================================================================================
+// FxArrowArray
+// ================================================================================================
+
+/// An array and its field in the C Data interface (`ArrowArray` & `ArrowSchema`).
+///
+/// Both are boxed so that their pointers stay valid while a consumer, e.g. pyarrow's
+/// `Array._import_from_c`, moves them out. Whatever is not moved out is released on drop.
+#[derive(Debug)]
+pub struct FxArrowArray {
+    pub array: Box<ArrowArray>,
+    pub schema: Box<ArrowSchema>,
+}
+
+impl FxArrowArray {
+    fn new(array: Box<dyn Array>, field: &Field) -> Self {
+        Self {
+            array: Box::new(ffi::export_array_to_c(array)),
+            schema: Box::new(ffi::export_field_to_c(field)),
+        }
+    }
+
+    /// Moves an array and its field out of the pointers given by a producer, leaving them
+    /// released.
+    ///
+    /// # Safety
+    /// Both pointers must be valid and follow the C Data interface.
+    pub unsafe fn from_raw(array: *mut ArrowArray, schema: *mut ArrowSchema) -> Self {
+        Self {
+            array: Box::new(std::ptr::replace(array, ArrowArray::empty())),
+            schema: Box::new(std::ptr::replace(schema, ArrowSchema::empty())),
+        }
+    }
+
+    pub fn array_ptr(&mut self) -> *mut ArrowArray {
+        self.array.as_mut()
+    }
+
+    pub fn schema_ptr(&mut self) -> *mut ArrowSchema {
+        self.schema.as_mut()
+    }
+
+    unsafe fn import(self) -> FxResult<(Field, Box<dyn Array>)> {
+        let field = ffi::import_field_from_c(&self.schema)?;
+        let array = ffi::import_array_from_c(*self.array, field.data_type.clone())?;
+
+        Ok((field, array))
+    }
+}
+
+// ================================================================================================
+// ArrowStreamChunks
+// ================================================================================================
+
+pub struct ArrowStreamChunks {
+    reader: ffi::ArrowArrayStreamReader,
+}
+
+impl Iterator for ArrowStreamChunks {
+    type Item = FxResult<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // safety: the stream is checked to be valid when importing
+        let array = unsafe { self.reader.next() }?;
+
+        let res = array
+            .map_err(FxError::from)
+            .and_then(struct_to_arrays)
+            .and_then(|arrays| Chunk::try_new(arrays).map_err(FxError::from));
+
+        Some(res)
+    }
+}
+
+// ================================================================================================
+// C Data interface
+// ================================================================================================
+
+impl FxIO {
+    /// Exports an array, the field is unnamed and nullable.
+    pub fn export_array(array: ArcArr) -> FxArrowArray {
+        let field = Field::new("", array.data_type().clone(), true);
+
+        FxArrowArray::new(array.to_boxed(), &field)
+    }
+
+    /// Imports an array.
+    ///
+    /// # Safety
+    /// `c` must follow the C Data interface, e.g. be produced by Arrow C++ or pyarrow.
+    pub unsafe fn import_array(c: FxArrowArray) -> FxResult<ArcArr> {
+        let (_, array) = c.import()?;
+
+        Ok(Arc::from(array))
+    }
+
+    /// Exports a chunk as a struct array, whose fields are taken from `schema`.
+    pub fn export_chunk(schema: &Schema, chunk: ChunkArr) -> FxResult<FxArrowArray> {
+        if schema.fields.len() != chunk.arrays().len() {
+            return Err(FxError::LengthMismatch(
+                schema.fields.len(),
+                chunk.arrays().len(),
+            ));
+        }
+
+        let arrays = chunk.into_arrays().iter().map(|a| a.to_boxed()).collect();
+        let array = arrays_to_struct(schema, arrays)?;
+
+        Ok(FxArrowArray::new(array, &schema_to_field(schema)))
+    }
+
+    /// Imports a struct array as a chunk and its schema.
+    ///
+    /// # Safety
+    /// `c` must follow the C Data interface, e.g. be produced by Arrow C++ or pyarrow.
+    pub unsafe fn import_chunk(c: FxArrowArray) -> FxResult<(Schema, ChunkArr)> {
+        let (field, array) = c.import()?;
+        let schema = field_to_schema(field)?;
+        let arrays = struct_to_arrays(array)?
+            .into_iter()
+            .map(Arc::from)
+            .collect();
+
+        Ok((schema, Chunk::try_new(arrays)?))
+    }
+
+    /// Exports an `Eclectic` with its schema as a struct array, e.g. `FxBatch`.
+    pub fn export_eclectic<D: Eclectic + Purport>(data: D) -> FxResult<FxArrowArray> {
+        let schema = data.schema().clone();
+        let arrays = data
+            .take_sequences()
+            .into_iter()
+            .map(FxSeq::to_box_array)
+            .collect::<FxResult<Vec<_>>>()?;
+        let array = arrays_to_struct(&schema, arrays)?;
+
+        Ok(FxArrowArray::new(array, &schema_to_field(&schema)))
+    }
+
+    /// Imports a struct array as an `Eclectic`, e.g. `FxBatch`.
+    ///
+    /// # Safety
+    /// `c` must follow the C Data interface, e.g. be produced by Arrow C++ or pyarrow.
+    pub unsafe fn import_eclectic<D: Eclectic>(c: FxArrowArray) -> FxResult<D> {
+        let (field, array) = c.import()?;
+        let schema = field_to_schema(field)?;
+        let chunk = Chunk::try_new(struct_to_arrays(array)?)?;
+
+        FxChunkIter::new(schema, std::iter::once(FxResult::Ok(chunk))).concat()
+    }
+}
+
+// ================================================================================================
+// C Stream interface
+// ================================================================================================
+
+impl FxIO {
+    /// Exports chunks one by one as struct arrays, lazily pulled by the consumer.
+    pub fn export_stream<E, I>(schema: Schema, data: I) -> Box<ArrowArrayStream>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: 'static,
+    {
+        let field = schema_to_field(&schema);
+        let arrays = data.into_iter().map(move |d| {
+            d.take_sequences()
+                .into_iter()
+                .map(FxSeq::to_box_array)
+                .collect::<FxResult<Vec<_>>>()
+                .and_then(|arrays| arrays_to_struct(&schema, arrays))
+                .map_err(|e| arrow2::error::Error::ExternalFormat(e.to_string()))
+        });
+
+        Box::new(ffi::export_iterator(Box::new(arrays), field))
+    }
+
+    /// Exports chunks of a receptacle, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn export_stream_from<C: IntoChunks>(data: C) -> FxResult<Box<ArrowArrayStream>> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Ok(Self::export_stream(schema, chunks))
+    }
+
+    /// Imports a stream of struct arrays lazily.
+    ///
+    /// # Safety
+    /// `stream` must follow the C Stream interface, e.g. be produced by Arrow C++ or pyarrow.
+    pub unsafe fn import_stream_iter<D: Eclectic>(
+        stream: Box<ArrowArrayStream>,
+    ) -> FxResult<FxChunkIter<ArrowStreamChunks, D>> {
+        let reader = ffi::ArrowArrayStreamReader::try_new(stream)?;
+        let schema = field_to_schema(reader.field().clone())?;
+
+        Ok(FxChunkIter::new(schema, ArrowStreamChunks { reader }))
+    }
+
+    /// Imports all arrays of a stream into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` &
+    /// `FxTabular`.
+    ///
+    /// # Safety
+    /// `stream` must follow the C Stream interface, e.g. be produced by Arrow C++ or pyarrow.
+    pub unsafe fn import_stream_into<C: FromChunks<D>, D: Eclectic>(
+        stream: Box<ArrowArrayStream>,
+    ) -> FxResult<C> {
+        Self::import_stream_iter::<D>(stream)?.collect_into()
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_ffi {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+    use crate::cont::{FxBatch, FxBatches};
+
+    #[test]
+    fn array_round_trip_success() {
+        let array = arc_arr!([Some(1.5f64), None, Some(-2.0)]);
+
+        let c = FxIO::export_array(array.clone());
+        let res = unsafe { FxIO::import_array(c) }.unwrap();
+        assert_eq!(res, array);
+
+        // a consumer moves the structs out of the pointers
+        let mut c = FxIO::export_array(array.clone());
+        let moved = unsafe { FxArrowArray::from_raw(c.array_ptr(), c.schema_ptr()) };
+        let res = unsafe { FxIO::import_array(moved) }.unwrap();
+        assert_eq!(res, array);
+    }
+
+    #[test]
+    fn chunk_round_trip_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+        let metadata = BTreeMap::from([("source".to_string(), "test".to_string())]);
+        let schema = data.schema().clone().with_metadata(metadata);
+        let chunk = ChunkArr::new(data.data().arrays().to_vec());
+
+        let c = FxIO::export_chunk(&schema, chunk.clone()).unwrap();
+        let (res_schema, res) = unsafe { FxIO::import_chunk(c) }.unwrap();
+        assert_eq!(res_schema, schema);
+        assert_eq!(res, chunk);
+
+        let err = FxIO::export_chunk(&Schema::from(vec![]), chunk).unwrap_err();
+        assert!(matches!(err, FxError::LengthMismatch(0, 2)));
+    }
+
+    #[test]
+    fn batch_round_trip_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+
+        let c = FxIO::export_eclectic(data.clone()).unwrap();
+        let res: FxBatch = unsafe { FxIO::import_eclectic(c) }.unwrap();
+
+        assert_eq!(res.schema(), data.schema());
+        assert_eq!(res.data(), data.data());
+
+        // only struct arrays are chunks
+        let c = FxIO::export_array(arc_arr!([1i64, 2]));
+        assert!(unsafe { FxIO::import_eclectic::<FxBatch>(c) }.is_err());
+    }
+
+    #[test]
+    fn stream_round_trip_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+        let stream = FxIO::export_stream(schema.clone(), vec![data.clone(), data.clone()]);
+
+        let iter = unsafe { FxIO::import_stream_iter::<FxBatch>(stream) }.unwrap();
+        assert_eq!(iter.schema(), &schema);
+        let res = iter.collect::<FxResult<Vec<_>>>().unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[1].data(), data.data());
+
+        let batches =
+            FxBatches::new_with_names(vec![data.clone(), data.clone(), data], ["c1", "c2"]);
+        let stream = FxIO::export_stream_from(batches).unwrap();
+        let res: FxBatches<FxBatch> = unsafe { FxIO::import_stream_into(stream) }.unwrap();
+        assert_eq!(res.schema(), &schema);
+        assert_eq!(res.data.len(), 3);
+    }
+}
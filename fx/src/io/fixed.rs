@@ -0,0 +1,543 @@
+//! file: fixed.rs
+//! author: Jacob Xie
+//! date: 2026/10/19 02:18:45 Monday
+//! brief: Fixed-width text I/O
+
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read, Write};
+
+use arrow2::array::{get_display, Array, BooleanArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::datatypes::{DataType, Field, Schema};
+
+use super::ab::FromChunks;
+use super::FxIO;
+use crate::ab::{Eclectic, FxChunkIter, FxSeq, Purport};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// Spec
+// ================================================================================================
+
+pub const DEFAULT_FIXED_WIDTH_BATCH_SIZE: usize = 8192;
+
+/// A column of a fixed-width line, positions are counted in chars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedWidthColumn {
+    pub name: String,
+    /// 0-based offset of the first char
+    pub start: usize,
+    pub width: usize,
+    pub data_type: DataType,
+    /// A cell equals to it (after trimming) is null, blank cells are always null
+    pub null_token: Option<String>,
+}
+
+impl FixedWidthColumn {
+    pub fn new<S: Into<String>>(name: S, start: usize, width: usize, data_type: DataType) -> Self {
+        Self {
+            name: name.into(),
+            start,
+            width,
+            data_type,
+            null_token: None,
+        }
+    }
+
+    pub fn with_null_token<S: Into<String>>(mut self, null_token: S) -> Self {
+        self.null_token = Some(null_token.into());
+        self
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.width
+    }
+
+    fn is_null(&self, cell: &str) -> bool {
+        cell.is_empty() || self.null_token.as_deref() == Some(cell)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FixedWidthOptions {
+    pub columns: Vec<FixedWidthColumn>,
+    /// Number of rows of each `Eclectic`
+    pub batch_size: usize,
+    /// Number of leading lines to skip, e.g. a header
+    pub skip_lines: usize,
+}
+
+impl FixedWidthOptions {
+    pub fn new(columns: Vec<FixedWidthColumn>) -> Self {
+        Self {
+            columns,
+            batch_size: DEFAULT_FIXED_WIDTH_BATCH_SIZE,
+            skip_lines: 0,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_skip_lines(mut self, skip_lines: usize) -> Self {
+        self.skip_lines = skip_lines;
+        self
+    }
+
+    pub fn schema(&self) -> Schema {
+        let fields = self
+            .columns
+            .iter()
+            .map(|c| Field::new(&c.name, c.data_type.clone(), true))
+            .collect::<Vec<_>>();
+
+        Schema::from(fields)
+    }
+
+    fn validate(&self) -> FxResult<()> {
+        if self.batch_size == 0 {
+            return Err(FxError::InvalidArgument(
+                "batch size should be greater than 0".to_string(),
+            ));
+        }
+
+        validate_columns(&self.columns)
+    }
+}
+
+fn validate_columns(columns: &[FixedWidthColumn]) -> FxResult<()> {
+    if columns.is_empty() {
+        return Err(FxError::InvalidArgument(
+            "fixed-width spec should have at least one column".to_string(),
+        ));
+    }
+
+    let mut names = HashSet::new();
+    for c in columns {
+        if c.width == 0 {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{}` should have a width greater than 0",
+                c.name
+            )));
+        }
+        if !names.insert(c.name.as_str()) {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{}` is duplicated",
+                c.name
+            )));
+        }
+    }
+
+    let mut sorted = columns.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|c| c.start);
+    for w in sorted.windows(2) {
+        if w[0].end() > w[1].start {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{}` overlaps column `{}`",
+                w[0].name, w[1].name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// ================================================================================================
+// FixedWidthChunks
+// ================================================================================================
+
+pub struct FixedWidthChunks<R: BufRead> {
+    reader: R,
+    columns: Vec<FixedWidthColumn>,
+    batch_size: usize,
+    // 1-based number of the last line read
+    line_no: usize,
+    buf: String,
+    done: bool,
+}
+
+impl<R: BufRead> FixedWidthChunks<R> {
+    fn read_line(&mut self) -> FxResult<bool> {
+        self.buf.clear();
+        if self.reader.read_line(&mut self.buf)? == 0 {
+            return Ok(false);
+        }
+        self.line_no += 1;
+
+        let len = self.buf.trim_end_matches(['\n', '\r']).len();
+        self.buf.truncate(len);
+
+        Ok(true)
+    }
+
+    fn read_chunk(&mut self) -> FxResult<Option<Chunk<Box<dyn Array>>>> {
+        let mut cells = vec![Vec::with_capacity(self.batch_size); self.columns.len()];
+        // physical line number of each row
+        let mut lines = Vec::with_capacity(self.batch_size);
+
+        while lines.len() < self.batch_size && self.read_line()? {
+            // blank lines are not rows
+            if self.buf.trim().is_empty() {
+                continue;
+            }
+
+            let chars = self.buf.chars().collect::<Vec<_>>();
+            for (c, col) in cells.iter_mut().zip(&self.columns) {
+                let start = col.start.min(chars.len());
+                let end = col.end().min(chars.len());
+                let cell = chars[start..end].iter().collect::<String>();
+                let cell = cell.trim();
+                c.push((!col.is_null(cell)).then(|| cell.to_string()));
+            }
+            lines.push(self.line_no);
+        }
+
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        let arrays = cells
+            .into_iter()
+            .zip(&self.columns)
+            .map(|(c, col)| parse_column(c, col, &lines))
+            .collect::<FxResult<Vec<_>>>()?;
+
+        Ok(Some(Chunk::try_new(arrays)?))
+    }
+}
+
+impl<R: BufRead> Iterator for FixedWidthChunks<R> {
+    type Item = FxResult<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.read_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn parse_error(line: usize, col: &FixedWidthColumn, cell: &str) -> FxError {
+    FxError::InvalidArgument(format!(
+        "line {line}, column `{}`: cannot parse `{cell}` as {:?}",
+        col.name, col.data_type
+    ))
+}
+
+fn parse_column(
+    cells: Vec<Option<String>>,
+    col: &FixedWidthColumn,
+    lines: &[usize],
+) -> FxResult<Box<dyn Array>> {
+    // arrow2 does not cast `Utf8` to `Boolean`
+    if col.data_type == DataType::Boolean {
+        let values = cells
+            .iter()
+            .zip(lines)
+            .map(|(c, l)| match c.as_deref() {
+                None => Ok(None),
+                Some(s) if s.eq_ignore_ascii_case("true") => Ok(Some(true)),
+                Some(s) if s.eq_ignore_ascii_case("false") => Ok(Some(false)),
+                Some(s) => Err(parse_error(*l, col, s)),
+            })
+            .collect::<FxResult<Vec<_>>>()?;
+
+        return Ok(BooleanArray::from(values).boxed());
+    }
+
+    let array = Utf8Array::<i32>::from(&cells);
+    if col.data_type == DataType::Utf8 {
+        return Ok(array.boxed());
+    }
+
+    let res = cast(&array, &col.data_type, CastOptions::default())
+        .map_err(|e| FxError::InvalidArgument(format!("column `{}`: {e}", col.name)))?;
+
+    // a cell failed to parse becomes null
+    if res.null_count() != array.null_count() {
+        for (idx, c) in cells.iter().enumerate() {
+            if let Some(s) = c {
+                if res.is_null(idx) {
+                    return Err(parse_error(lines[idx], col, s));
+                }
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+// ================================================================================================
+// Fixed-width
+// ================================================================================================
+
+impl FxIO {
+    /// Reads an `Eclectic` of `batch_size` rows lazily, blank lines are skipped.
+    pub fn read_fixed_width_iter<D: Eclectic, R: Read>(
+        reader: R,
+        options: FixedWidthOptions,
+    ) -> FxResult<FxChunkIter<FixedWidthChunks<BufReader<R>>, D>> {
+        options.validate()?;
+        let schema = options.schema();
+
+        let mut chunks = FixedWidthChunks {
+            reader: BufReader::new(reader),
+            columns: options.columns,
+            batch_size: options.batch_size,
+            line_no: 0,
+            buf: String::new(),
+            done: false,
+        };
+        for _ in 0..options.skip_lines {
+            if !chunks.read_line()? {
+                break;
+            }
+        }
+
+        Ok(FxChunkIter::new(schema, chunks))
+    }
+
+    /// Reads all lines into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` & `FxTabular`.
+    pub fn read_fixed_width_into<C: FromChunks<D>, D: Eclectic, R: Read>(
+        reader: R,
+        options: FixedWidthOptions,
+    ) -> FxResult<C> {
+        Self::read_fixed_width_iter::<D, R>(reader, options)?.collect_into()
+    }
+
+    /// Reads all lines into one `Eclectic`.
+    pub fn read_fixed_width<D: Eclectic, R: Read>(
+        reader: R,
+        options: FixedWidthOptions,
+    ) -> FxResult<D> {
+        Self::read_fixed_width_iter::<D, R>(reader, options)?.concat()
+    }
+
+    /// Writes one line per row, columns of `data` are picked by the names of `columns`.
+    ///
+    /// Numbers are right-aligned and others left-aligned, gaps are filled with spaces. Nulls
+    /// are written as the null token or blank.
+    pub fn write_fixed_width<D: Eclectic + Purport, W: Write>(
+        data: D,
+        mut writer: W,
+        columns: &[FixedWidthColumn],
+    ) -> FxResult<()> {
+        validate_columns(columns)?;
+
+        let names = data.names();
+        let indices = columns
+            .iter()
+            .map(|c| {
+                names.iter().position(|n| n == &c.name).ok_or_else(|| {
+                    FxError::InvalidArgument(format!("column `{}` not found", c.name))
+                })
+            })
+            .collect::<FxResult<Vec<_>>>()?;
+
+        let arrays = data
+            .take_sequences()
+            .into_iter()
+            .map(FxSeq::to_box_array)
+            .collect::<FxResult<Vec<_>>>()?;
+        let arrays = indices
+            .iter()
+            .map(|&i| arrays[i].clone())
+            .collect::<Vec<_>>();
+
+        let len = arrays.iter().map(|a| a.len()).max().unwrap_or(0);
+        let mut sorted = columns.iter().zip(&arrays).collect::<Vec<_>>();
+        sorted.sort_by_key(|(c, _)| c.start);
+
+        let mut line = String::new();
+        let mut cell = String::new();
+        for row in 0..len {
+            line.clear();
+            let mut pos = 0;
+            for (col, arr) in sorted.iter() {
+                cell.clear();
+                if row >= arr.len() || arr.is_null(row) {
+                    cell.push_str(col.null_token.as_deref().unwrap_or(""));
+                } else {
+                    get_display(arr.as_ref(), "")(&mut cell, row)
+                        .map_err(|e| FxError::InvalidArgument(e.to_string()))?;
+                }
+
+                let width = cell.chars().count();
+                if width > col.width {
+                    return Err(FxError::InvalidArgument(format!(
+                        "row {}, column `{}`: `{cell}` exceeds the width {}",
+                        row + 1,
+                        col.name,
+                        col.width
+                    )));
+                }
+
+                line.push_str(&" ".repeat(col.start - pos));
+                if is_numeric(&col.data_type) {
+                    line.push_str(&format!("{cell:>0$}", col.width));
+                } else {
+                    line.push_str(&format!("{cell:<0$}", col.width));
+                }
+                pos = col.end();
+            }
+
+            writeln!(writer, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal(_, _)
+    )
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_fixed {
+    use super::*;
+    use crate::ab::{FromSlice, Purport};
+    use crate::arc_arr;
+    use crate::cont::{FxBatch, FxBatches};
+
+    fn columns() -> Vec<FixedWidthColumn> {
+        vec![
+            FixedWidthColumn::new("id", 0, 4, DataType::Int64),
+            FixedWidthColumn::new("name", 5, 6, DataType::Utf8).with_null_token("NA"),
+            FixedWidthColumn::new("score", 12, 6, DataType::Float64).with_null_token("-"),
+            FixedWidthColumn::new("ok", 19, 5, DataType::Boolean),
+        ]
+    }
+
+    const TEXT: &str = "\
+  ID NAME    SCORE OK
+   1 alice     1.5 true
+   2 NA          - false
+
+  30 bob     -2.25
+";
+
+    #[test]
+    fn fixed_width_read_success() {
+        let options = FixedWidthOptions::new(columns()).with_skip_lines(1);
+        let res = FxIO::read_fixed_width::<FxBatch, _>(TEXT.as_bytes(), options).unwrap();
+
+        let expected = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), Some(2), Some(30)]),
+                arc_arr!([Some("alice"), None, Some("bob")]),
+                arc_arr!([Some(1.5f64), None, Some(-2.25)]),
+                arc_arr!([Some(true), Some(false), None]),
+            ],
+            ["id", "name", "score", "ok"],
+        );
+
+        assert_eq!(res.names(), vec!["id", "name", "score", "ok"]);
+        assert_eq!(res.data(), expected.data());
+    }
+
+    #[test]
+    fn fixed_width_batch_size_success() {
+        let options = FixedWidthOptions::new(columns())
+            .with_skip_lines(1)
+            .with_batch_size(2);
+
+        let iter = FxIO::read_fixed_width_iter::<FxBatch, _>(TEXT.as_bytes(), options).unwrap();
+        let lens = iter.map(|b| b.unwrap().max_len()).collect::<Vec<_>>();
+        assert_eq!(lens, vec![Some(2), Some(1)]);
+
+        let options = FixedWidthOptions::new(columns())
+            .with_skip_lines(1)
+            .with_batch_size(2);
+        let res: FxBatches<FxBatch> =
+            FxIO::read_fixed_width_into::<_, FxBatch, _>(TEXT.as_bytes(), options).unwrap();
+        assert_eq!(res.data.len(), 2);
+    }
+
+    #[test]
+    fn fixed_width_parse_error_fail() {
+        let text = "   1 alice    1.5\n   x bob      2.0\n";
+        let options = FixedWidthOptions::new(columns());
+        let err = FxIO::read_fixed_width::<FxBatch, _>(text.as_bytes(), options).unwrap_err();
+        assert!(matches!(
+            err,
+            FxError::InvalidArgument(m) if m == "line 2, column `id`: cannot parse `x` as Int64"
+        ));
+
+        let text = "ID\n   1 alice    1.5  yes\n";
+        let options = FixedWidthOptions::new(columns()).with_skip_lines(1);
+        let err = FxIO::read_fixed_width::<FxBatch, _>(text.as_bytes(), options).unwrap_err();
+        assert!(matches!(
+            err,
+            FxError::InvalidArgument(m) if m.starts_with("line 2, column `ok`:")
+        ));
+
+        let overlapped = vec![
+            FixedWidthColumn::new("a", 0, 4, DataType::Int64),
+            FixedWidthColumn::new("b", 3, 4, DataType::Int64),
+        ];
+        assert!(FixedWidthOptions::new(overlapped).validate().is_err());
+    }
+
+    #[test]
+    fn fixed_width_write_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), Some(2), Some(30)]),
+                arc_arr!([Some("alice"), None, Some("bob")]),
+                arc_arr!([Some(1.5f64), None, Some(-2.25)]),
+                arc_arr!([Some(true), Some(false), None]),
+            ],
+            ["id", "name", "score", "ok"],
+        );
+
+        let mut buf = Vec::new();
+        FxIO::write_fixed_width(data.clone(), &mut buf, &columns()).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "   1 alice     1.5 true\n   2 NA          - false\n  30 bob     -2.25\n"
+        );
+
+        let options = FixedWidthOptions::new(columns());
+        let res = FxIO::read_fixed_width::<FxBatch, _>(buf.as_slice(), options).unwrap();
+        assert_eq!(res.data(), data.data());
+
+        let narrow = vec![FixedWidthColumn::new("name", 0, 3, DataType::Utf8)];
+        let err = FxIO::write_fixed_width(data, &mut Vec::new(), &narrow).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "row 1, column `name`: `alice` exceeds the width 3"
+        );
+    }
+}
@@ -0,0 +1,556 @@
+//! file: format.rs
+//! author: Jacob Xie
+//! date: 2026/10/19 00:21:36 Monday
+//! brief: Format-agnostic I/O
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use arrow2::io::csv::write as csv_write;
+use arrow2::io::ipc::write as ipc_write;
+
+use super::{ec::ReadSeek, AvroReadOptions, AvroWriteOptions, CsvReadOptions, FxIO};
+use super::{DecompressReader, FxCompression};
+use super::{ParquetReadOptions, ParquetWriteOptions};
+use crate::ab::{Eclectic, Purport};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// FxFormat
+// ================================================================================================
+
+const MAGIC_PARQUET: &[u8] = b"PAR1";
+const MAGIC_IPC: &[u8] = b"ARROW1";
+const MAGIC_AVRO: &[u8] = b"Obj\x01";
+// IPC streams start with a continuation marker
+const MAGIC_IPC_STREAM: &[u8] = &[0xff, 0xff, 0xff, 0xff];
+
+// bytes read to detect text formats
+const SNIFF_LEN: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FxFormat {
+    Csv,
+    /// an array of objects
+    Json,
+    /// newline-delimited objects
+    Ndjson,
+    /// Arrow IPC file
+    Ipc,
+    /// Arrow IPC stream
+    IpcStream,
+    Avro,
+    Parquet,
+    /// detected by the path's extension, or the leading bytes
+    Auto,
+}
+
+impl FxFormat {
+    /// Detects the format by the extension of a path, case-insensitive, the extension of a
+    /// compression is skipped, e.g. `.csv.gz`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let path = path.as_ref();
+        let path = match FxCompression::from_path(path) {
+            Some(_) => Path::new(path.file_stem()?),
+            None => path,
+        };
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match ext.as_str() {
+            "csv" => Some(FxFormat::Csv),
+            "json" => Some(FxFormat::Json),
+            "ndjson" | "jsonl" => Some(FxFormat::Ndjson),
+            "arrow" | "ipc" | "feather" => Some(FxFormat::Ipc),
+            "arrows" => Some(FxFormat::IpcStream),
+            "avro" => Some(FxFormat::Avro),
+            "parquet" | "pq" => Some(FxFormat::Parquet),
+            _ => None,
+        }
+    }
+
+    /// Detects the format by the leading bytes: binary formats by their magic bytes, JSON & NDJSON
+    /// by the first non-whitespace character, otherwise UTF-8 text is taken as CSV.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(MAGIC_PARQUET) {
+            return Some(FxFormat::Parquet);
+        }
+        if bytes.starts_with(MAGIC_IPC) {
+            return Some(FxFormat::Ipc);
+        }
+        if bytes.starts_with(MAGIC_AVRO) {
+            return Some(FxFormat::Avro);
+        }
+        if bytes.starts_with(MAGIC_IPC_STREAM) {
+            return Some(FxFormat::IpcStream);
+        }
+
+        match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+            None => None,
+            Some(b'[') => Some(FxFormat::Json),
+            Some(b'{') => Some(FxFormat::Ndjson),
+            // a multi-byte character may be cut at the end
+            Some(_) => match std::str::from_utf8(bytes) {
+                Ok(_) => Some(FxFormat::Csv),
+                Err(e) if e.error_len().is_none() => Some(FxFormat::Csv),
+                Err(_) => None,
+            },
+        }
+    }
+}
+
+// ================================================================================================
+// FxSource & FxSink
+// ================================================================================================
+
+/// Where to read from: a file path, or any seekable reader.
+pub enum FxSource<'a> {
+    Path(PathBuf),
+    Reader(Box<dyn ReadSeek + 'a>),
+}
+
+impl<'a> FxSource<'a> {
+    pub fn reader<R: ReadSeek + 'a>(reader: R) -> Self {
+        FxSource::Reader(Box::new(reader))
+    }
+}
+
+/// Where to write to: a file path, or any writer.
+pub enum FxSink<'a> {
+    Path(PathBuf),
+    Writer(Box<dyn Write + 'a>),
+}
+
+impl<'a> FxSink<'a> {
+    pub fn writer<W: Write + 'a>(writer: W) -> Self {
+        FxSink::Writer(Box::new(writer))
+    }
+}
+
+macro_rules! impl_from_path {
+    ($t:ident) => {
+        impl From<&str> for $t<'_> {
+            fn from(path: &str) -> Self {
+                $t::Path(PathBuf::from(path))
+            }
+        }
+
+        impl From<String> for $t<'_> {
+            fn from(path: String) -> Self {
+                $t::Path(PathBuf::from(path))
+            }
+        }
+
+        impl From<&Path> for $t<'_> {
+            fn from(path: &Path) -> Self {
+                $t::Path(path.to_path_buf())
+            }
+        }
+
+        impl From<PathBuf> for $t<'_> {
+            fn from(path: PathBuf) -> Self {
+                $t::Path(path)
+            }
+        }
+    };
+}
+
+impl_from_path!(FxSource);
+impl_from_path!(FxSink);
+
+impl From<File> for FxSource<'_> {
+    fn from(file: File) -> Self {
+        FxSource::reader(file)
+    }
+}
+
+impl From<File> for FxSink<'_> {
+    fn from(file: File) -> Self {
+        FxSink::writer(file)
+    }
+}
+
+// ================================================================================================
+// FxReadOptions & FxWriteOptions
+//
+// Options of each format, only the ones of the format read or written are used.
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct FxReadOptions {
+    pub csv: CsvReadOptions,
+    pub avro: AvroReadOptions,
+    pub parquet: ParquetReadOptions,
+}
+
+impl FxReadOptions {
+    pub fn with_csv(mut self, options: CsvReadOptions) -> Self {
+        self.csv = options;
+        self
+    }
+
+    pub fn with_avro(mut self, options: AvroReadOptions) -> Self {
+        self.avro = options;
+        self
+    }
+
+    pub fn with_parquet(mut self, options: ParquetReadOptions) -> Self {
+        self.parquet = options;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FxWriteOptions {
+    pub csv: Option<csv_write::SerializeOptions>,
+    /// used by both IPC file & stream
+    pub ipc: Option<ipc_write::WriteOptions>,
+    pub avro: AvroWriteOptions,
+    pub parquet: ParquetWriteOptions,
+    /// compression of the whole stream, also detected by the path's extension, e.g. `.csv.gz`
+    pub compression: Option<FxCompression>,
+}
+
+impl FxWriteOptions {
+    pub fn with_csv(mut self, options: csv_write::SerializeOptions) -> Self {
+        self.csv = Some(options);
+        self
+    }
+
+    pub fn with_ipc(mut self, options: ipc_write::WriteOptions) -> Self {
+        self.ipc = Some(options);
+        self
+    }
+
+    pub fn with_avro(mut self, options: AvroWriteOptions) -> Self {
+        self.avro = options;
+        self
+    }
+
+    pub fn with_parquet(mut self, options: ParquetWriteOptions) -> Self {
+        self.parquet = options;
+        self
+    }
+
+    pub fn with_compression(mut self, compression: FxCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+}
+
+// ================================================================================================
+// FxIO
+// ================================================================================================
+
+impl FxIO {
+    /// Reads a file or a reader into one `Eclectic`, see `read_with`.
+    pub fn read<'a, D, S>(source: S, format: FxFormat) -> FxResult<D>
+    where
+        D: Eclectic + Purport,
+        S: Into<FxSource<'a>>,
+    {
+        Self::read_with(source, format, FxReadOptions::default())
+    }
+
+    /// Reads a file or a reader into one `Eclectic`.
+    ///
+    /// `FxFormat::Auto` detects the format by the path's extension first, then by the leading
+    /// bytes of the content.
+    pub fn read_with<'a, D, S>(source: S, format: FxFormat, options: FxReadOptions) -> FxResult<D>
+    where
+        D: Eclectic + Purport,
+        S: Into<FxSource<'a>>,
+    {
+        match source.into() {
+            FxSource::Path(path) => {
+                let file = BufReader::new(File::open(&path)?);
+                let mut reader = DecompressReader::try_new(file)?;
+                let format = match (format, FxFormat::from_path(&path)) {
+                    (FxFormat::Auto, Some(f)) => f,
+                    (FxFormat::Auto, None) => detect_format(&mut reader)?.ok_or_else(|| {
+                        FxError::InvalidArgument(format!(
+                            "cannot detect the format: `{}`",
+                            path.display()
+                        ))
+                    })?,
+                    (f, _) => f,
+                };
+
+                read_format(reader, format, options)
+            }
+            FxSource::Reader(reader) => {
+                let mut reader = DecompressReader::try_new(reader)?;
+                let format = match format {
+                    FxFormat::Auto => detect_format(&mut reader)?.ok_or_else(|| {
+                        FxError::InvalidArgument("cannot detect the format".to_string())
+                    })?,
+                    f => f,
+                };
+
+                read_format(reader, format, options)
+            }
+        }
+    }
+
+    /// Writes one `Eclectic` into a file or a writer, see `write_with`.
+    pub fn write<'a, D, S>(data: D, sink: S, format: FxFormat) -> FxResult<()>
+    where
+        D: Eclectic + Purport,
+        S: Into<FxSink<'a>>,
+    {
+        Self::write_with(data, sink, format, FxWriteOptions::default())
+    }
+
+    /// Writes one `Eclectic` into a file or a writer.
+    ///
+    /// `FxFormat::Auto` detects the format by the path's extension, hence a writer requires an
+    /// explicit format.
+    pub fn write_with<'a, D, S>(
+        data: D,
+        sink: S,
+        format: FxFormat,
+        options: FxWriteOptions,
+    ) -> FxResult<()>
+    where
+        D: Eclectic + Purport,
+        S: Into<FxSink<'a>>,
+    {
+        match sink.into() {
+            FxSink::Path(path) => {
+                let format = match format {
+                    FxFormat::Auto => FxFormat::from_path(&path).ok_or_else(|| {
+                        FxError::InvalidArgument(format!(
+                            "cannot detect the format: `{}`",
+                            path.display()
+                        ))
+                    })?,
+                    f => f,
+                };
+                let compression = options.compression.or(FxCompression::from_path(&path));
+
+                write_compressed(data, File::create(&path)?, format, compression, options)
+            }
+            FxSink::Writer(writer) => {
+                if format == FxFormat::Auto {
+                    return Err(FxError::InvalidArgument(
+                        "cannot detect the format of a writer".to_string(),
+                    ));
+                }
+                let compression = options.compression;
+
+                write_compressed(data, writer, format, compression, options)
+            }
+        }
+    }
+}
+
+fn read_format<D, R>(mut reader: R, format: FxFormat, options: FxReadOptions) -> FxResult<D>
+where
+    D: Eclectic + Purport,
+    R: ReadSeek,
+{
+    match format {
+        FxFormat::Csv => FxIO::read_csv_iter::<D, _>(reader, options.csv)?.concat(),
+        FxFormat::Json => FxIO::read_json(reader),
+        FxFormat::Ndjson => FxIO::read_ndjson(reader),
+        FxFormat::Ipc => FxIO::read_ipc(reader),
+        FxFormat::IpcStream => FxIO::read_ipc_streaming(reader),
+        FxFormat::Avro => FxIO::read_avro_with(&mut reader, options.avro),
+        FxFormat::Parquet => FxIO::read_parquet_with(&mut reader, options.parquet),
+        FxFormat::Auto => Err(FxError::InvalidArgument(
+            "`FxFormat::Auto` is not a concrete format".to_string(),
+        )),
+    }
+}
+
+// sniffs the leading bytes, and rewinds the reader
+fn detect_format<R: ReadSeek>(reader: &mut R) -> FxResult<Option<FxFormat>> {
+    let start = reader.stream_position()?;
+
+    let mut buf = Vec::with_capacity(SNIFF_LEN);
+    reader
+        .by_ref()
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut buf)?;
+    reader.seek(SeekFrom::Start(start))?;
+
+    if buf.is_empty() {
+        return Err(FxError::EmptyContent);
+    }
+
+    Ok(FxFormat::from_bytes(&buf))
+}
+
+fn write_compressed<D, W>(
+    data: D,
+    writer: W,
+    format: FxFormat,
+    compression: Option<FxCompression>,
+    options: FxWriteOptions,
+) -> FxResult<()>
+where
+    D: Eclectic + Purport,
+    W: Write,
+{
+    let mut writer = BufWriter::new(writer);
+    match compression {
+        Some(c) => {
+            let mut writer = c.encode_with(writer, |w| write_format(data, w, format, options))?;
+            writer.flush()?;
+        }
+        None => {
+            write_format(data, &mut writer, format, options)?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_format<D, W>(data: D, writer: W, format: FxFormat, options: FxWriteOptions) -> FxResult<()>
+where
+    D: Eclectic + Purport,
+    W: Write,
+{
+    match format {
+        FxFormat::Csv => FxIO::write_csv(data, writer, options.csv.as_ref()),
+        FxFormat::Json => FxIO::write_json(data, writer),
+        FxFormat::Ndjson => FxIO::write_ndjson(data, writer),
+        FxFormat::Ipc => FxIO::write_ipc(data, writer, options.ipc),
+        FxFormat::IpcStream => FxIO::write_ipc_streaming(data, writer, options.ipc),
+        FxFormat::Avro => FxIO::write_avro_with(data, writer, options.avro),
+        FxFormat::Parquet => FxIO::write_parquet_with(data, writer, options.parquet),
+        FxFormat::Auto => Err(FxError::InvalidArgument(
+            "`FxFormat::Auto` is not a concrete format".to_string(),
+        )),
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_format {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+    use crate::cont::FxBatch;
+
+    #[test]
+    fn format_detect_success() {
+        assert_eq!(FxFormat::from_path("a/b.PARQUET"), Some(FxFormat::Parquet));
+        assert_eq!(FxFormat::from_path("b.jsonl"), Some(FxFormat::Ndjson));
+        assert_eq!(FxFormat::from_path("b.arrows"), Some(FxFormat::IpcStream));
+        assert_eq!(FxFormat::from_path("b.txt"), None);
+        assert_eq!(FxFormat::from_path("b"), None);
+
+        assert_eq!(FxFormat::from_bytes(b"PAR1\x15"), Some(FxFormat::Parquet));
+        assert_eq!(FxFormat::from_bytes(b"ARROW1\0\0"), Some(FxFormat::Ipc));
+        assert_eq!(FxFormat::from_bytes(b"Obj\x01\x04"), Some(FxFormat::Avro));
+        assert_eq!(
+            FxFormat::from_bytes(b" \n[{\"a\":1}]"),
+            Some(FxFormat::Json)
+        );
+        assert_eq!(FxFormat::from_bytes(b"{\"a\":1}\n"), Some(FxFormat::Ndjson));
+        assert_eq!(FxFormat::from_bytes(b"a,b\n1,2\n"), Some(FxFormat::Csv));
+        assert_eq!(FxFormat::from_bytes(b"\x00\x9f\x92\x96x"), None);
+        assert_eq!(FxFormat::from_bytes(b"  "), None);
+    }
+
+    #[test]
+    fn read_write_auto_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+                arc_arr!([1.5, 2.5, 3.5]),
+            ],
+            ["c1", "c2", "c3"],
+        );
+
+        let formats = [
+            FxFormat::Csv,
+            FxFormat::Json,
+            FxFormat::Ndjson,
+            FxFormat::Ipc,
+            FxFormat::IpcStream,
+            FxFormat::Avro,
+            FxFormat::Parquet,
+        ];
+        for format in formats {
+            let mut buf = Vec::new();
+            FxIO::write(data.clone(), FxSink::writer(&mut buf), format).unwrap();
+
+            // detected by the leading bytes
+            let res: FxBatch = FxIO::read(FxSource::reader(Cursor::new(&buf)), FxFormat::Auto)
+                .unwrap_or_else(|e| panic!("{format:?}: {e}"));
+            assert_eq!(res.names(), vec!["c1", "c2", "c3"], "{format:?}");
+            assert_eq!(res.max_len(), Some(3), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn read_write_path_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1i64), None, Some(3)]),
+                arc_arr!([Some("a"), Some("b"), None]),
+                arc_arr!([1.5, 2.5, 3.5]),
+            ],
+            ["c1", "c2", "c3"],
+        );
+
+        for ext in [
+            "csv", "json", "ndjson", "arrow", "arrows", "avro", "parquet",
+        ] {
+            let path = format!("./cache/format.{ext}");
+            FxIO::write(data.clone(), path.as_str(), FxFormat::Auto).unwrap();
+
+            let res: FxBatch = FxIO::read(path.as_str(), FxFormat::Auto).unwrap();
+            assert_eq!(res.names(), vec!["c1", "c2", "c3"], "{ext}");
+            assert_eq!(res.max_len(), Some(3), "{ext}");
+        }
+
+        // no extension, detected by the magic bytes
+        let path = "./cache/format_parquet";
+        FxIO::write(data.clone(), path, FxFormat::Parquet).unwrap();
+        let res: FxBatch = FxIO::read(path, FxFormat::Auto).unwrap();
+        assert_eq!(res.max_len(), Some(3));
+
+        // options of the format
+        let options =
+            FxReadOptions::default().with_parquet(ParquetReadOptions::default().with_limit(2));
+        let res: FxBatch = FxIO::read_with(path, FxFormat::Parquet, options).unwrap();
+        assert_eq!(res.max_len(), Some(2));
+    }
+
+    #[test]
+    fn read_write_error() {
+        let data = FxBatch::new_with_names(vec![arc_arr!([1, 2])], ["c1"]);
+
+        // a writer requires an explicit format
+        let res = FxIO::write(data.clone(), FxSink::writer(Vec::new()), FxFormat::Auto);
+        assert!(matches!(res, Err(FxError::InvalidArgument(_))));
+
+        // unknown extension
+        let res = FxIO::write(data, "./cache/format.txt", FxFormat::Auto);
+        assert!(matches!(res, Err(FxError::InvalidArgument(_))));
+
+        // undetectable content
+        let source = FxSource::reader(Cursor::new(vec![0u8, 0x9f, 0x92, 0x96]));
+        let res = FxIO::read::<FxBatch, _>(source, FxFormat::Auto);
+        assert!(matches!(res, Err(FxError::InvalidArgument(_))));
+
+        // empty content
+        let source = FxSource::reader(Cursor::new(Vec::new()));
+        let res = FxIO::read::<FxBatch, _>(source, FxFormat::Auto);
+        assert!(matches!(res, Err(FxError::EmptyContent)));
+
+        // missing file
+        let res = FxIO::read::<FxBatch, _>("./cache/format_missing.csv", FxFormat::Auto);
+        assert!(matches!(res, Err(FxError::StdIO(_))));
+    }
+}
@@ -3,15 +3,27 @@
 //! date: 2023/03/26 08:06:20 Sunday
 //! brief:
 
-use std::io::Write;
+use std::fs::File;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
 
+use arrow2::array::Array;
 use arrow2::chunk::Chunk;
+use arrow2::datatypes::Schema;
 use arrow2::io::ipc::read as ipc_read;
 use arrow2::io::ipc::write as ipc_write;
+use arrow2::mmap;
+use futures::{AsyncRead, AsyncSeek, AsyncWrite, SinkExt, Stream, StreamExt};
+use memmap2::Mmap;
 
 use super::{ec::ReadSeek, FxIO, SimpleIO};
-use crate::ab::{Congruent, Eclectic, FxSeq, Purport};
+use crate::ab::{
+    Congruent, Dqs, Eclectic, FromChunks, FxChunkIter, FxChunkStream, FxSeq, IntoChunks, Purport,
+};
+use crate::cont::FxTabular;
 use crate::error::{FxError, FxResult};
+use crate::io::ab::eclectic_to_sized_chunks;
 
 // ================================================================================================
 // Arrow
@@ -44,24 +56,543 @@ impl FxIO {
         Ok(())
     }
 
-    pub fn read_ipc<D: Eclectic + Purport, R: ReadSeek>(mut reader: R) -> FxResult<D> {
+    /// Writes one record batch per chunk, chunks longer than `max_rows` are split.
+    pub fn write_ipc_iter<E, I, W>(
+        schema: Schema,
+        data: I,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let default_options = ipc_write::WriteOptions { compression: None };
+        let opt = options.unwrap_or(default_options);
+
+        let mut writer = ipc_write::FileWriter::new(writer, schema, None, opt);
+
+        writer.start()?;
+        for d in data {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                writer.write(&chunk, None)?;
+            }
+        }
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes one record batch per chunk of a receptacle, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn write_ipc_from<C: IntoChunks, W: Write>(
+        data: C,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_ipc_iter(schema, chunks, writer, max_rows, options)
+    }
+
+    /// Reads all record batches lazily.
+    pub fn read_ipc_iter<D: Eclectic, R: ReadSeek>(
+        reader: R,
+    ) -> FxResult<FxChunkIter<ipc_read::FileReader<R>, D>> {
+        Self::read_ipc_iter_with(reader, IpcReadOptions::default())
+    }
+
+    /// Reads all record batches lazily, see `IpcReadOptions`.
+    pub fn read_ipc_iter_with<D: Eclectic, R: ReadSeek>(
+        mut reader: R,
+        options: IpcReadOptions,
+    ) -> FxResult<FxChunkIter<ipc_read::FileReader<R>, D>> {
         let metadata = ipc_read::read_file_metadata(&mut reader)?;
+        let schema = metadata.schema.clone();
+
+        let reader = ipc_read::FileReader::new(reader, metadata, None, None);
+
+        let iter = FxChunkIter::new(schema, reader);
+        match &options.expected_schema {
+            Some(expected) => iter.expect_schema(expected),
+            None => Ok(iter),
+        }
+    }
+
+    /// Reads all record batches into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` & `FxTabular`.
+    pub fn read_ipc_into<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(reader: R) -> FxResult<C> {
+        Self::read_ipc_iter::<D, R>(reader)?.collect_into()
+    }
+
+    /// Reads all record batches into a receptacle, see `IpcReadOptions`.
+    pub fn read_ipc_into_with<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(
+        reader: R,
+        options: IpcReadOptions,
+    ) -> FxResult<C> {
+        Self::read_ipc_iter_with::<D, R>(reader, options)?.collect_into()
+    }
+
+    /// Reads all record batches and concatenates them into one `Eclectic`.
+    pub fn read_ipc<D: Eclectic + Purport, R: ReadSeek>(reader: R) -> FxResult<D> {
+        Self::read_ipc_iter::<D, R>(reader)?.concat()
+    }
+
+    /// Reads all record batches into one `Eclectic`, see `IpcReadOptions`.
+    pub fn read_ipc_with<D: Eclectic + Purport, R: ReadSeek>(
+        reader: R,
+        options: IpcReadOptions,
+    ) -> FxResult<D> {
+        Self::read_ipc_iter_with::<D, R>(reader, options)?.concat()
+    }
+}
 
-        let reader = ipc_read::FileReader::new(&mut reader, metadata, None, None);
+// ================================================================================================
+// IpcReadOptions
+// ================================================================================================
 
-        let mut res = None;
-        // only loop once, since ipc's file write only write one batch
-        for maybe_chunk in reader {
-            let data = maybe_chunk?
-                .into_arrays()
-                .into_iter()
-                .map(FxSeq::from_box_arr)
-                .collect::<Vec<_>>();
+#[derive(Debug, Clone, Default)]
+pub struct IpcReadOptions {
+    /// the file's schema is validated against it, see `check_schema`
+    pub expected_schema: Option<Schema>,
+}
+
+impl IpcReadOptions {
+    pub fn with_expected_schema(mut self, schema: Schema) -> Self {
+        self.expected_schema = Some(schema);
+        self
+    }
+}
+
+// ================================================================================================
+// Arrow streaming format
+//
+// Unlike the file format, the streaming format has no footer, hence it can be written to and read
+// from pipes & sockets, without `Seek`.
+// ================================================================================================
 
-            res = Some(D::from_vec_seq(data));
+pub struct IpcStreamChunks<R: Read> {
+    reader: ipc_read::StreamReader<R>,
+    // see `FxIO::read_ipc_streaming_tail_iter`
+    tailing: bool,
+    failed: bool,
+}
+
+impl<R: Read> Iterator for IpcStreamChunks<R> {
+    type Item = arrow2::error::Result<Chunk<Box<dyn Array>>>;
+
+    // EOF without the end-of-stream marker is a truncated stream, unless the stream is tailed
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
         }
 
-        res.unwrap()
+        match self.reader.next()? {
+            Ok(ipc_read::StreamState::Some(chunk)) => Some(Ok(chunk)),
+            Ok(ipc_read::StreamState::Waiting) if self.tailing => None,
+            Ok(ipc_read::StreamState::Waiting) => {
+                self.failed = true;
+                Some(Err(arrow2::error::Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "stream ended without the end-of-stream marker",
+                ))))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl FxIO {
+    pub fn write_ipc_streaming<D: Eclectic + Purport, W: Write>(
+        data: D,
+        writer: W,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
+
+        Self::write_ipc_streaming_iter(schema, [data], writer, None, options)
+    }
+
+    /// Writes one record batch per chunk in the streaming format, chunks longer than `max_rows`
+    /// are split.
+    pub fn write_ipc_streaming_iter<E, I, W>(
+        schema: Schema,
+        data: I,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let mut writer = FxIpcWriter::try_new_streaming(writer, schema, options)?;
+        for d in data {
+            writer.append_sized(d, max_rows)?;
+        }
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Reads all record batches of the streaming format lazily.
+    /// A stream truncated before its end-of-stream marker yields an error.
+    pub fn read_ipc_streaming_iter<D: Eclectic, R: Read>(
+        reader: R,
+    ) -> FxResult<FxChunkIter<IpcStreamChunks<R>, D>> {
+        Self::ipc_streaming_iter(reader, false)
+    }
+
+    /// Same as `read_ipc_streaming_iter`, but for a live stream (e.g. a growing file): reaching
+    /// the current end without the end-of-stream marker yields `None`, and the iterator can be
+    /// polled again once more data arrives.
+    pub fn read_ipc_streaming_tail_iter<D: Eclectic, R: Read>(
+        reader: R,
+    ) -> FxResult<FxChunkIter<IpcStreamChunks<R>, D>> {
+        Self::ipc_streaming_iter(reader, true)
+    }
+
+    fn ipc_streaming_iter<D: Eclectic, R: Read>(
+        mut reader: R,
+        tailing: bool,
+    ) -> FxResult<FxChunkIter<IpcStreamChunks<R>, D>> {
+        let metadata = ipc_read::read_stream_metadata(&mut reader)?;
+        let schema = metadata.schema.clone();
+
+        let reader = ipc_read::StreamReader::new(reader, metadata, None);
+        let chunks = IpcStreamChunks {
+            reader,
+            tailing,
+            failed: false,
+        };
+
+        Ok(FxChunkIter::new(schema, chunks))
+    }
+
+    /// Reads all record batches of the streaming format and concatenates them into one
+    /// `Eclectic`.
+    pub fn read_ipc_streaming<D: Eclectic, R: Read>(reader: R) -> FxResult<D> {
+        Self::read_ipc_streaming_iter::<D, R>(reader)?.concat()
+    }
+}
+
+// ================================================================================================
+// FxIpcWriter
+//
+// A long-lived writer, record batches are appended as they arrive and the footer (file format) or
+// the end-of-stream marker (streaming format) is written by `finish`.
+// ================================================================================================
+
+enum IpcWriterInner<W: Write> {
+    File(ipc_write::FileWriter<W>),
+    Streaming(ipc_write::StreamWriter<W>),
+}
+
+pub struct FxIpcWriter<W: Write> {
+    schema: Schema,
+    inner: IpcWriterInner<W>,
+    // number of `FxTabular`'s arrays already written, see `append_tabular`
+    tabular_written: usize,
+}
+
+impl<W: Write> FxIpcWriter<W> {
+    /// Writer of the file format.
+    pub fn try_new(
+        writer: W,
+        schema: Schema,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<Self> {
+        let options = options.unwrap_or(ipc_write::WriteOptions { compression: None });
+        let inner = ipc_write::FileWriter::try_new(writer, schema.clone(), None, options)?;
+
+        Ok(Self {
+            schema,
+            inner: IpcWriterInner::File(inner),
+            tabular_written: 0,
+        })
+    }
+
+    /// Writer of the streaming format.
+    pub fn try_new_streaming(
+        writer: W,
+        schema: Schema,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<Self> {
+        let options = options.unwrap_or(ipc_write::WriteOptions { compression: None });
+        let mut inner = ipc_write::StreamWriter::new(writer, options);
+        inner.start(&schema, None)?;
+
+        Ok(Self {
+            schema,
+            inner: IpcWriterInner::Streaming(inner),
+            tabular_written: 0,
+        })
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn write_chunk(&mut self, chunk: &Chunk<Box<dyn Array>>) -> FxResult<()> {
+        if chunk.arrays().len() != self.schema.fields.len() {
+            return Err(FxError::LengthMismatch(
+                chunk.arrays().len(),
+                self.schema.fields.len(),
+            ));
+        }
+        let matched = chunk
+            .arrays()
+            .iter()
+            .zip(self.schema.fields.iter())
+            .all(|(a, f)| a.data_type() == f.data_type());
+        if !matched {
+            return Err(FxError::SchemaMismatch);
+        }
+
+        match &mut self.inner {
+            IpcWriterInner::File(w) => w.write(chunk, None)?,
+            IpcWriterInner::Streaming(w) => w.write(chunk, None)?,
+        }
+
+        Ok(())
+    }
+
+    /// Appends one record batch.
+    pub fn append<E: Eclectic>(&mut self, data: E) -> FxResult<()> {
+        self.append_sized(data, None)
+    }
+
+    /// Appends record batches of at most `max_rows` rows.
+    pub fn append_sized<E: Eclectic>(&mut self, data: E, max_rows: Option<usize>) -> FxResult<()> {
+        for chunk in eclectic_to_sized_chunks(data, max_rows)? {
+            self.write_chunk(&chunk)?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends the aligned batches of an `FxTabular` which have not been written yet, and returns
+    /// the number of batches written. Batches are tracked by position, hence the `FxTabular` is
+    /// expected to grow by `push_back` only.
+    pub fn append_tabular(&mut self, data: &FxTabular) -> FxResult<usize> {
+        let start = self.tabular_written;
+        let len = data.min_deque_len().unwrap_or(0);
+
+        for idx in start..len {
+            let arrays = data
+                .ref_data()
+                .iter()
+                .map(|dq| {
+                    dq.get(idx)
+                        .ok_or(FxError::OutBounds)?
+                        .clone()
+                        .to_box_array()
+                })
+                .collect::<FxResult<Vec<_>>>()?;
+            let chunk = Chunk::try_new(arrays)?;
+
+            self.write_chunk(&chunk)?;
+            self.tabular_written += 1;
+        }
+
+        Ok(self.tabular_written - start)
+    }
+
+    /// Writes the footer (file format) or the end-of-stream marker (streaming format), and
+    /// returns the inner writer.
+    pub fn finish(self) -> FxResult<W> {
+        let writer = match self.inner {
+            IpcWriterInner::File(mut w) => {
+                w.finish()?;
+                w.into_inner()
+            }
+            IpcWriterInner::Streaming(mut w) => {
+                w.finish()?;
+                w.into_inner()
+            }
+        };
+
+        Ok(writer)
+    }
+}
+
+// ================================================================================================
+// Memory-mapped Arrow
+//
+// Buffers of the arrays point into the mapped file instead of being copied, i.e. opening a file is
+// cheap regardless of its size, and pages are loaded by the OS as they are accessed.
+// ================================================================================================
+
+pub struct FxIpcMmap {
+    data: Arc<Mmap>,
+    metadata: ipc_read::FileMetadata,
+    dictionaries: ipc_read::Dictionaries,
+}
+
+impl FxIpcMmap {
+    /// Maps an uncompressed Arrow IPC file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, i.e. while this value or any of the
+    /// arrays read from it is alive. Array buffers are not validated, hence the file should be
+    /// written by a trusted Arrow writer, e.g. `FxIO::write_ipc`.
+    pub unsafe fn try_new<P: AsRef<Path>>(path: P) -> FxResult<Self> {
+        let file = File::open(path)?;
+        let data = Arc::new(Mmap::map(&file)?);
+
+        let metadata = ipc_read::read_file_metadata(&mut Cursor::new(data.as_ref().as_ref()))?;
+        let dictionaries = mmap::mmap_dictionaries_unchecked(&metadata, data.clone())?;
+
+        Ok(Self {
+            data,
+            metadata,
+            dictionaries,
+        })
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.metadata.schema
+    }
+
+    pub fn num_chunks(&self) -> usize {
+        self.metadata.blocks.len()
+    }
+
+    /// Maps the `idx`th record batch, fails if the file is compressed.
+    pub fn chunk(&self, idx: usize) -> FxResult<Chunk<Box<dyn Array>>> {
+        if idx >= self.num_chunks() {
+            return Err(FxError::OutBounds);
+        }
+
+        // safety: guaranteed by `try_new`
+        let chunk = unsafe {
+            mmap::mmap_unchecked(&self.metadata, &self.dictionaries, self.data.clone(), idx)?
+        };
+
+        Ok(chunk)
+    }
+}
+
+pub struct IpcMmapChunks {
+    mmap: FxIpcMmap,
+    idx: usize,
+}
+
+impl Iterator for IpcMmapChunks {
+    type Item = FxResult<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.mmap.num_chunks() {
+            return None;
+        }
+
+        let res = self.mmap.chunk(self.idx);
+        self.idx += 1;
+
+        Some(res)
+    }
+}
+
+impl FxIO {
+    /// Maps all record batches lazily.
+    ///
+    /// # Safety
+    ///
+    /// Same as `FxIpcMmap::try_new`.
+    pub unsafe fn read_ipc_mmap_iter<D: Eclectic, P: AsRef<Path>>(
+        path: P,
+    ) -> FxResult<FxChunkIter<IpcMmapChunks, D>> {
+        let mmap = FxIpcMmap::try_new(path)?;
+        let schema = mmap.schema().clone();
+
+        Ok(FxChunkIter::new(schema, IpcMmapChunks { mmap, idx: 0 }))
+    }
+
+    /// Maps all record batches into a receptacle without copying, e.g. `FxBatches` & `FxTabular`.
+    ///
+    /// # Safety
+    ///
+    /// Same as `FxIpcMmap::try_new`.
+    pub unsafe fn read_ipc_mmap_into<C: FromChunks<D>, D: Eclectic, P: AsRef<Path>>(
+        path: P,
+    ) -> FxResult<C> {
+        Self::read_ipc_mmap_iter::<D, P>(path)?.collect_into()
+    }
+}
+
+// ================================================================================================
+// Arrow async
+// ================================================================================================
+
+impl FxIO {
+    pub async fn write_ipc_async<D: Eclectic + Purport, W: AsyncWrite + Unpin + Send>(
+        data: D,
+        writer: W,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
+
+        Self::write_ipc_stream(schema, futures::stream::iter([data]), writer, None, options).await
+    }
+
+    /// Writes one record batch per chunk of a stream, chunks longer than `max_rows` are split.
+    pub async fn write_ipc_stream<E, S, W>(
+        schema: Schema,
+        data: S,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        S: Stream<Item = E>,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let default_options = ipc_write::WriteOptions { compression: None };
+        let opt = options.unwrap_or(default_options);
+
+        let mut sink = ipc_write::file_async::FileSink::new(writer, schema, None, opt);
+
+        let mut data = std::pin::pin!(data);
+        while let Some(d) = data.next().await {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                sink.feed(chunk.into()).await?;
+            }
+        }
+        sink.close().await?;
+
+        Ok(())
+    }
+
+    /// Reads all record batches lazily.
+    pub async fn read_ipc_stream<'a, D, R>(
+        mut reader: R,
+    ) -> FxResult<FxChunkStream<ipc_read::file_async::FileStream<'a>, D>>
+    where
+        D: Eclectic,
+        R: AsyncRead + AsyncSeek + Unpin + Send + 'a,
+    {
+        let metadata = ipc_read::file_async::read_file_metadata_async(&mut reader).await?;
+        let schema = metadata.schema.clone();
+
+        let stream = ipc_read::file_async::FileStream::new(reader, metadata, None, None);
+
+        Ok(FxChunkStream::new(schema, stream))
+    }
+
+    /// Reads all record batches and concatenates them into one `Eclectic`.
+    pub async fn read_ipc_async<D, R>(reader: R) -> FxResult<D>
+    where
+        D: Eclectic,
+        R: AsyncRead + AsyncSeek + Unpin + Send,
+    {
+        Self::read_ipc_stream::<D, R>(reader).await?.concat().await
     }
 }
 
@@ -71,24 +602,48 @@ impl FxIO {
 
 impl<T: Eclectic + Purport> SimpleIO<T> {
     pub fn write_ipc(&mut self, options: Option<ipc_write::WriteOptions>) -> FxResult<()> {
-        if self.data.is_none() || self.writer.is_none() {
+        self.write_data(|data, writer| FxIO::write_ipc(data, writer, options))
+    }
+
+    pub fn read_ipc(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
             return Err(FxError::EmptyContent);
         }
 
-        let writer = self.writer.take().unwrap();
-        let data = self.take_data().unwrap();
+        let mut reader = self.reader.take().unwrap();
 
-        FxIO::write_ipc(data, writer, options)
+        self.data = Some(FxIO::read_ipc::<T, _>(&mut reader)?);
+
+        Ok(())
     }
 
-    pub fn read_ipc(&mut self) -> FxResult<()> {
+    pub fn read_ipc_with(&mut self, options: IpcReadOptions) -> FxResult<()> {
         if self.reader.is_none() {
             return Err(FxError::EmptyContent);
         }
 
         let mut reader = self.reader.take().unwrap();
 
-        self.data = Some(FxIO::read_ipc::<T, _>(&mut reader)?);
+        self.data = Some(FxIO::read_ipc_with::<T, _>(&mut reader, options)?);
+
+        Ok(())
+    }
+
+    pub fn write_ipc_streaming(
+        &mut self,
+        options: Option<ipc_write::WriteOptions>,
+    ) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_ipc_streaming(data, writer, options))
+    }
+
+    pub fn read_ipc_streaming(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
+            return Err(FxError::EmptyContent);
+        }
+
+        let reader = self.reader.take().unwrap();
+
+        self.data = Some(FxIO::read_ipc_streaming::<T, _>(reader)?);
 
         Ok(())
     }
@@ -166,4 +721,295 @@ mod test_ipc {
 
         println!("{:?}", ref_data.unwrap());
     }
+
+    #[test]
+    fn ipc_read_multi_chunks_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches, FxTabular};
+
+        let batch = FxBatch::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        let chunk = Chunk::new(
+            batch
+                .data()
+                .arrays()
+                .iter()
+                .map(|a| a.to_boxed())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        let opt = ipc_write::WriteOptions { compression: None };
+        let mut writer = ipc_write::FileWriter::new(&mut buf, batch.schema().clone(), None, opt);
+        writer.start().unwrap();
+        writer.write(&chunk, None).unwrap();
+        writer.write(&chunk, None).unwrap();
+        writer.finish().unwrap();
+
+        buf.set_position(0);
+        let res = FxIO::read_ipc::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+
+        buf.set_position(0);
+        let res: FxBatches<FxBatch> = FxIO::read_ipc_into(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data.len(), 2);
+
+        buf.set_position(0);
+        let res: Vec<ChunkArr> = FxIO::read_ipc_into(&mut buf).unwrap();
+        assert_eq!(res.len(), 2);
+
+        buf.set_position(0);
+        let res: FxTabular = FxIO::read_ipc_into::<_, ChunkArr, _>(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+
+        buf.set_position(0);
+        let iter = FxIO::read_ipc_iter::<ChunkArr, _>(&mut buf).unwrap();
+        assert_eq!(iter.count(), 2);
+
+        // validated by the expected schema
+        buf.set_position(0);
+        let options = IpcReadOptions::default().with_expected_schema(batch.schema().clone());
+        let res = FxIO::read_ipc_with::<FxBatch, _>(&mut buf, options).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+
+        buf.set_position(0);
+        let unexpected = Schema::from(vec![batch.schema().fields[1].clone()]);
+        let options = IpcReadOptions::default().with_expected_schema(unexpected);
+        assert!(FxIO::read_ipc_with::<FxBatch, _>(&mut buf, options).is_err());
+
+        // empty input
+        let res = FxIO::read_ipc::<FxBatch, _>(Cursor::new(Vec::<u8>::new()));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn ipc_write_record_batches_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches};
+
+        let batches = FxBatches::new_with_names(
+            vec![
+                ChunkArr::new(vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])]),
+                ChunkArr::new(vec![arc_arr!([4]), arc_arr!(["d"])]),
+            ],
+            ["c1", "c2"],
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_ipc_from(batches, &mut buf, None, None).unwrap();
+
+        buf.set_position(0);
+        let res: FxBatches<FxBatch> = FxIO::read_ipc_into(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data.len(), 2);
+        assert_eq!(res.data[1].names(), vec!["c1", "c2"]);
+
+        // zero is not a valid `max_rows`
+        let batches = FxBatches::new(vec![ChunkArr::new(vec![arc_arr!([1])])]);
+        let res = FxIO::write_ipc_from(batches, Cursor::new(Vec::new()), Some(0), None);
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn ipc_async_round_trip_success() {
+        use futures::io::Cursor;
+        use futures::TryStreamExt;
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1), None, Some(3)]),
+                arc_arr!(["a", "b", "c"]),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        let stream = futures::stream::iter([data.clone(), data]);
+        FxIO::write_ipc_stream(schema, stream, &mut buf, Some(2), None)
+            .await
+            .unwrap();
+
+        buf.set_position(0);
+        let batches = FxIO::read_ipc_stream::<FxBatch, _>(&mut buf)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[0].names(), vec!["c1", "c2"]);
+
+        buf.set_position(0);
+        let res = FxIO::read_ipc_async::<FxBatch, _>(&mut buf).await.unwrap();
+        assert_eq!(res.max_len(), Some(6));
+    }
+
+    #[test]
+    fn ipc_streaming_round_trip_success() {
+        let data = FxBatch::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let mut buf = Vec::new();
+        FxIO::write_ipc_streaming_iter(schema, [data.clone(), data], &mut buf, Some(2), None)
+            .unwrap();
+
+        // no `Seek` required
+        let batches = FxIO::read_ipc_streaming_iter::<FxBatch, _>(buf.as_slice())
+            .unwrap()
+            .collect::<FxResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[3].names(), vec!["c1", "c2"]);
+
+        let res = FxIO::read_ipc_streaming::<FxBatch, _>(buf.as_slice()).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+
+        // without the end-of-stream marker (8 bytes): truncated, unless tailed
+        let truncated = &buf[..buf.len() - 8];
+        assert!(FxIO::read_ipc_streaming::<FxBatch, _>(truncated).is_err());
+        let mut iter = FxIO::read_ipc_streaming_iter::<FxBatch, _>(truncated).unwrap();
+        assert_eq!(iter.by_ref().filter(Result::is_ok).count(), 4);
+        assert!(iter.next().is_none());
+
+        let batches = FxIO::read_ipc_streaming_tail_iter::<FxBatch, _>(truncated)
+            .unwrap()
+            .collect::<FxResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 4);
+
+        // the file format is not readable as the streaming format
+        let mut file = Vec::new();
+        FxIO::write_ipc(res, &mut file, None).unwrap();
+        assert!(FxIO::read_ipc_streaming::<FxBatch, _>(file.as_slice()).is_err());
+    }
+
+    #[test]
+    fn ipc_compression_success() {
+        use std::io::Cursor;
+
+        let data = FxBatch::new_with_names(
+            vec![arc_arr!((0..1000).collect::<Vec<i64>>().as_slice())],
+            ["c1"],
+        );
+
+        for compression in [ipc_write::Compression::LZ4, ipc_write::Compression::ZSTD] {
+            let options = ipc_write::WriteOptions {
+                compression: Some(compression),
+            };
+
+            let mut buf = Cursor::new(Vec::new());
+            FxIO::write_ipc(data.clone(), &mut buf, Some(options)).unwrap();
+
+            buf.set_position(0);
+            let res = FxIO::read_ipc::<FxBatch, _>(&mut buf).unwrap();
+            assert_eq!(res.data(), data.data());
+        }
+    }
+
+    #[test]
+    fn ipc_writer_append_tabular_success() {
+        use std::io::Cursor;
+
+        use crate::ab::Dqs;
+
+        let mut data =
+            FxTabular::new_with_names(vec![arc_arr!([1, 2]), arc_arr!(["a", "b"])], ["c1", "c2"]);
+
+        let mut writer =
+            FxIpcWriter::try_new(Cursor::new(Vec::new()), data.schema().clone(), None).unwrap();
+        assert_eq!(writer.append_tabular(&data).unwrap(), 1);
+        assert_eq!(writer.append_tabular(&data).unwrap(), 0);
+
+        // batches arrive
+        data.push_back(vec![arc_arr!([3]), arc_arr!(["c"])])
+            .unwrap();
+        data.push_back(vec![arc_arr!([4, 5]), arc_arr!(["d", "e"])])
+            .unwrap();
+        assert_eq!(writer.append_tabular(&data).unwrap(), 2);
+
+        // mismatched types
+        assert!(writer
+            .append(FxBatch::new(vec![arc_arr!(["x"]), arc_arr!([1])]))
+            .is_err());
+        writer
+            .append(FxBatch::new(vec![arc_arr!([6]), arc_arr!(["f"])]))
+            .unwrap();
+
+        let mut buf = writer.finish().unwrap();
+        buf.set_position(0);
+        let res: Vec<FxBatch> = FxIO::read_ipc_into(&mut buf).unwrap();
+        assert_eq!(
+            res.iter().map(|b| b.max_len()).collect::<Vec<_>>(),
+            vec![Some(2), Some(1), Some(2), Some(1)]
+        );
+    }
+
+    #[test]
+    fn ipc_mmap_success() {
+        use arrow2::array::PrimitiveArray;
+
+        use crate::ab::Receptacle;
+        use crate::cont::{ChunkArr, FxBatches};
+
+        const FILE_IPC_MMAP: &str = "./cache/test_mmap.arrow";
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!((0..1000).collect::<Vec<i64>>().as_slice()),
+                arc_arr!((0..1000)
+                    .map(|i| format!("s{i}"))
+                    .collect::<Vec<_>>()
+                    .as_slice()),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let file = std::fs::File::create(FILE_IPC_MMAP).unwrap();
+        FxIO::write_ipc_iter(schema, [data.clone()], file, Some(300), None).unwrap();
+
+        let mmap = unsafe { FxIpcMmap::try_new(FILE_IPC_MMAP) }.unwrap();
+        assert_eq!(mmap.num_chunks(), 4);
+        assert!(mmap.chunk(4).is_err());
+
+        // buffers point into the mapped file
+        let range = mmap.data.as_ptr_range();
+        let chunk = mmap.chunk(1).unwrap();
+        let values = chunk[0]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .unwrap()
+            .values();
+        assert!(range.contains(&(values.as_ptr() as *const u8)));
+        assert_eq!(values[0], 300);
+
+        let batches: FxBatches<FxBatch> =
+            unsafe { FxIO::read_ipc_mmap_into(FILE_IPC_MMAP) }.unwrap();
+        assert_eq!(batches.get(3).unwrap().max_len(), Some(100));
+        assert!(batches.get(4).is_err());
+
+        let tabular: FxTabular =
+            unsafe { FxIO::read_ipc_mmap_into::<_, ChunkArr, _>(FILE_IPC_MMAP) }.unwrap();
+        assert_eq!(tabular.names(), vec!["c1", "c2"]);
+        assert_eq!(tabular.deque_lens(), vec![4, 4]);
+
+        // compressed files cannot be mapped
+        let options = ipc_write::WriteOptions {
+            compression: Some(ipc_write::Compression::LZ4),
+        };
+        let file = std::fs::File::create(FILE_IPC_MMAP).unwrap();
+        FxIO::write_ipc(data, file, Some(options)).unwrap();
+        let mmap = unsafe { FxIpcMmap::try_new(FILE_IPC_MMAP) }.unwrap();
+        assert!(mmap.chunk(0).is_err());
+    }
 }
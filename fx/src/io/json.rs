@@ -0,0 +1,336 @@
+//! file: json.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 22:14:03 Sunday
+//! brief: JSON & NDJSON I/O
+
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+
+use arrow2::array::{Array, StructArray};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Schema};
+use arrow2::io::json::read as json_read;
+use arrow2::io::json::write as json_write;
+use arrow2::io::ndjson::read as ndjson_read;
+use arrow2::io::ndjson::read::FallibleStreamingIterator;
+use arrow2::io::ndjson::write as ndjson_write;
+
+use super::compress::{decompress_bytes, DecompressReader};
+use super::{ec::ReadSeek, FxIO, SimpleIO};
+use crate::ab::{Eclectic, FromChunks, FxChunkIter, FxSeq, IntoChunks, Purport};
+use crate::error::{FxError, FxResult};
+
+// ================================================================================================
+// Rows <-> StructArray
+//
+// Each JSON object is a row, i.e. an element of a `StructArray` whose fields are the columns.
+// ================================================================================================
+
+fn eclectic_to_struct<E: Eclectic>(schema: &Schema, data: E) -> FxResult<Box<dyn Array>> {
+    let arrays = data
+        .take_sequences()
+        .into_iter()
+        .map(FxSeq::to_box_array)
+        .collect::<FxResult<Vec<_>>>()?;
+
+    chunk_to_struct(schema, Chunk::try_new(arrays)?)
+}
+
+fn chunk_to_struct(schema: &Schema, chunk: Chunk<Box<dyn Array>>) -> FxResult<Box<dyn Array>> {
+    let datatype = DataType::Struct(schema.fields.clone());
+
+    Ok(StructArray::try_new(datatype, chunk.into_arrays(), None)?.boxed())
+}
+
+fn struct_to_chunk(array: Box<dyn Array>) -> FxResult<(Schema, Chunk<Box<dyn Array>>)> {
+    let array = array
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .ok_or_else(|| FxError::InvalidArgument("rows should be JSON objects".to_string()))?;
+    let (fields, arrays, _) = array.clone().into_data();
+
+    Ok((Schema::from(fields), Chunk::try_new(arrays)?))
+}
+
+// ================================================================================================
+// NdjsonChunks
+// ================================================================================================
+
+pub struct NdjsonChunks<R: BufRead> {
+    reader: ndjson_read::FileReader<R>,
+    datatype: DataType,
+}
+
+impl<R: BufRead> Iterator for NdjsonChunks<R> {
+    type Item = arrow2::error::Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rows = match self.reader.next() {
+            Ok(Some(rows)) => rows,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let res = ndjson_read::deserialize(rows, self.datatype.clone()).and_then(|array| {
+            let array = array
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .ok_or_else(|| {
+                    arrow2::error::Error::ExternalFormat("rows should be JSON objects".to_string())
+                })?;
+            Chunk::try_new(array.values().to_vec())
+        });
+
+        Some(res)
+    }
+}
+
+// ================================================================================================
+// JSON
+// ================================================================================================
+
+impl FxIO {
+    /// Writes an array of objects, one object per row.
+    pub fn write_json<D: Eclectic + Purport, W: Write>(data: D, mut writer: W) -> FxResult<()> {
+        let schema = data.schema().clone();
+        let array = eclectic_to_struct(&schema, data)?;
+
+        let blocks = json_write::Serializer::new(vec![Ok(array)].into_iter(), vec![]);
+        json_write::write(&mut writer, blocks)?;
+
+        Ok(())
+    }
+
+    /// Reads an array of objects, columns are inferred from all the objects.
+    ///
+    /// Gzip or zstd compressed input is decoded.
+    pub fn read_json<D: Eclectic, R: Read>(mut reader: R) -> FxResult<D> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        let buf = decompress_bytes(buf)?;
+
+        let json = json_read::json_deserializer::parse(&buf).map_err(arrow2::error::Error::from)?;
+        let datatype = json_read::infer(&json)?;
+        if !matches!(&datatype, DataType::List(f) if matches!(f.data_type(), DataType::Struct(_))) {
+            return Err(FxError::InvalidArgument(
+                "JSON should be an array of objects".to_string(),
+            ));
+        }
+
+        // the outer array is unwrapped, i.e. a `StructArray` of all the objects
+        let array = json_read::deserialize(&json, datatype)?;
+        let (schema, chunk) = struct_to_chunk(array)?;
+
+        FxChunkIter::new(schema, std::iter::once(FxResult::Ok(chunk))).concat()
+    }
+
+    /// Writes one object per line.
+    pub fn write_ndjson<D: Eclectic + Purport, W: Write>(data: D, writer: W) -> FxResult<()> {
+        let schema = data.schema().clone();
+
+        Self::write_ndjson_iter(schema, [data], writer)
+    }
+
+    /// Writes chunks one by one, one object per line.
+    pub fn write_ndjson_iter<E, I, W>(schema: Schema, data: I, writer: W) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let arrays = data.into_iter().map(|d| {
+            eclectic_to_struct(&schema, d)
+                .map_err(|e| arrow2::error::Error::ExternalFormat(e.to_string()))
+        });
+        let serializer = ndjson_write::Serializer::new(arrays, vec![]);
+
+        for r in ndjson_write::FileWriter::new(writer, serializer) {
+            r?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes chunks of a receptacle one by one, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn write_ndjson_from<C: IntoChunks, W: Write>(data: C, writer: W) -> FxResult<()> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_ndjson_iter(schema, chunks, writer)
+    }
+
+    /// Reads batches of `batch_size` lines lazily, columns are inferred from the first
+    /// `infer_rows` lines (all lines if `None`), gzip or zstd compressed input is decoded.
+    pub fn read_ndjson_iter<D: Eclectic, R: ReadSeek>(
+        reader: R,
+        infer_rows: Option<usize>,
+        batch_size: usize,
+    ) -> FxResult<FxChunkIter<NdjsonChunks<BufReader<DecompressReader<R>>>, D>> {
+        if batch_size == 0 {
+            return Err(FxError::InvalidArgument(
+                "batch size should be greater than 0".to_string(),
+            ));
+        }
+
+        let mut reader = DecompressReader::try_new(reader)?;
+        let start = reader.stream_position()?;
+        let mut reader = BufReader::new(reader);
+        let datatype = ndjson_read::infer(&mut reader, infer_rows)?;
+        let DataType::Struct(fields) = &datatype else {
+            return Err(FxError::InvalidArgument(
+                "lines should be JSON objects".to_string(),
+            ));
+        };
+        let schema = Schema::from(fields.clone());
+        reader.seek(SeekFrom::Start(start))?;
+
+        let chunks = NdjsonChunks {
+            reader: ndjson_read::FileReader::new(reader, vec![String::new(); batch_size], None),
+            datatype,
+        };
+
+        Ok(FxChunkIter::new(schema, chunks))
+    }
+
+    /// Reads all lines into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` & `FxTabular`.
+    pub fn read_ndjson_into<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(
+        reader: R,
+        batch_size: usize,
+    ) -> FxResult<C> {
+        Self::read_ndjson_iter::<D, R>(reader, None, batch_size)?.collect_into()
+    }
+
+    /// Reads all lines into one `Eclectic`, gzip or zstd compressed input is decoded.
+    pub fn read_ndjson<D: Eclectic, R: ReadSeek>(reader: R) -> FxResult<D> {
+        Self::read_ndjson_iter::<D, _>(reader, None, 8192)?.concat()
+    }
+}
+
+// ================================================================================================
+// SimpleIO
+// ================================================================================================
+
+impl<T: Eclectic + Purport> SimpleIO<T> {
+    pub fn write_json(&mut self) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_json(data, writer))
+    }
+
+    pub fn read_json(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
+            return Err(FxError::EmptyContent);
+        }
+
+        let reader = self.reader.take().unwrap();
+
+        self.data = Some(FxIO::read_json::<T, _>(reader)?);
+
+        Ok(())
+    }
+
+    pub fn write_ndjson(&mut self) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_ndjson(data, writer))
+    }
+
+    pub fn read_ndjson(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
+            return Err(FxError::EmptyContent);
+        }
+
+        let reader = self.reader.take().unwrap();
+
+        self.data = Some(FxIO::read_ndjson::<T, _>(reader)?);
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_json {
+
+    use std::io::Cursor;
+
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+    use crate::cont::FxBatch;
+
+    use super::*;
+
+    const FILE_JSON: &str = "./cache/test.json";
+
+    #[test]
+    fn json_round_trip_success() {
+        let a = arc_arr!([Some(1i64), None, Some(3)]);
+        let b = arc_arr!(["x", "y", "z"]);
+        let data = FxBatch::new_with_names(vec![a, b], ["id", "sym"]);
+
+        let mut buf = Vec::new();
+        FxIO::write_json(data, &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            r#"[{"id":1,"sym":"x"},{"id":null,"sym":"y"},{"id":3,"sym":"z"}]"#
+        );
+
+        let res = FxIO::read_json::<FxBatch, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(res.names(), vec!["id", "sym"]);
+        assert_eq!(res.max_len(), Some(3));
+        assert_eq!(res.data()[0].null_count(), 1);
+
+        // not an array of objects
+        assert!(FxIO::read_json::<FxBatch, _>(Cursor::new("[1, 2]")).is_err());
+    }
+
+    #[test]
+    fn ndjson_nested_success() {
+        let lines = r#"{"id":1,"tags":["a","b"],"meta":{"score":1.5}}
+{"id":2,"tags":[],"meta":{"score":null}}
+{"id":3,"tags":null,"meta":{"score":3.0}}
+"#;
+
+        let res = FxIO::read_ndjson::<FxBatch, _>(Cursor::new(lines)).unwrap();
+        assert_eq!(res.names(), vec!["id", "tags", "meta"]);
+        assert!(matches!(
+            res.schema().fields[1].data_type(),
+            DataType::List(_)
+        ));
+        assert!(matches!(
+            res.schema().fields[2].data_type(),
+            DataType::Struct(_)
+        ));
+
+        let mut buf = Vec::new();
+        FxIO::write_ndjson(res.clone(), &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf.clone()).unwrap(), lines);
+
+        // batches of 2 lines
+        let batches = FxIO::read_ndjson_iter::<FxBatch, _>(Cursor::new(buf), Some(1), 2)
+            .unwrap()
+            .collect::<FxResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].max_len(), Some(1));
+    }
+
+    #[test]
+    fn simple_write_read() {
+        let arrays = vec![
+            arc_arr!(["a", "c", "x"]),
+            arc_arr!([Some(2.1), None, Some(6.2)]),
+            arc_arr!([true, false, false]),
+        ];
+        let batch = FxBatch::new_with_names(arrays, ["c1", "c2", "c3"]);
+
+        let mut simple = SimpleIO::new_with_data(batch);
+        simple.set_file_writer(FILE_JSON).unwrap();
+        simple.write_json().unwrap();
+
+        let mut simple = SimpleIO::<FxBatch>::new();
+        simple.set_file_reader(FILE_JSON).unwrap();
+        simple.read_json().unwrap();
+
+        let res = simple.data().unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2", "c3"]);
+        assert_eq!(res.data()[1].null_count(), 1);
+    }
+}
@@ -5,17 +5,33 @@
 
 pub mod ab;
 pub mod arvo;
+pub mod compress;
 pub mod csv;
+pub mod dataset;
 pub mod ec;
+pub mod excel;
+pub mod ffi;
+pub mod fixed;
+pub mod format;
 pub mod ipc;
+pub mod json;
 pub mod parquet;
+pub mod predicate;
 pub mod sql;
 
 pub use arvo::*;
+pub use compress::*;
 pub use csv::*;
+pub use dataset::*;
 pub use ec::*;
+pub use excel::*;
+pub use ffi::*;
+pub use fixed::*;
+pub use format::*;
 pub use ipc::*;
+pub use json::*;
 pub use parquet::*;
+pub use predicate::*;
 pub use sql::*;
 
 /// A public struct used for implementing serval type of I/O
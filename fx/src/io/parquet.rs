@@ -3,15 +3,31 @@
 //! date: 2023/01/18 20:12:29 Wednesday
 //! brief: Parquet I/O
 
+use std::collections::HashMap;
 use std::io::Write;
 
+use arrow2::array::{Array, PrimitiveArray};
+use arrow2::chunk::Chunk;
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::compute::concatenate::concatenate;
+use arrow2::compute::filter::filter_chunk;
+use arrow2::datatypes::{DataType, Field, IntegerType, Schema};
 use arrow2::io::parquet::read as parquet_read;
 use arrow2::io::parquet::write as parquet_write;
+use futures::stream::BoxStream;
+use futures::{
+    AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, SinkExt, Stream, StreamExt,
+};
 
-use super::{ec::ReadSeek, FxIO, SimpleIO};
-use crate::ab::{Congruent, Eclectic, FxSeq, Purport};
+use super::ec::{par_map_ordered, ReadSeek};
+use super::predicate::{array_values, ColumnStats, FxPredicate};
+use super::{FxIO, ParallelIO, SimpleIO};
+use crate::ab::{Congruent, Eclectic, FromChunks, FxChunkIter, FxChunkStream, IntoChunks, Purport};
+use crate::cont::ChunkArr;
 use crate::error::FxError;
 use crate::error::FxResult;
+use crate::io::ab::eclectic_to_sized_chunks;
+use crate::value::FxValue;
 
 // ================================================================================================
 // Parquet
@@ -20,73 +36,733 @@ use crate::error::FxResult;
 impl FxIO {
     // single threaded
     pub fn write_parquet<D: Eclectic + Purport, W: Write>(
+        data: D,
+        writer: W,
+        options: Option<parquet_write::WriteOptions>,
+    ) -> FxResult<()> {
+        let options = options.map(Into::into).unwrap_or_default();
+
+        Self::write_parquet_with(data, writer, options)
+    }
+
+    /// Writes with per column encodings, compression & statistics, see `ParquetWriteOptions`.
+    pub fn write_parquet_with<D: Eclectic + Purport, W: Write>(
         data: D,
         mut writer: W,
+        options: ParquetWriteOptions,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
+        let specs = options.column_specs(&schema)?;
+        let parquet_fields = parquet_write::to_parquet_schema(&schema)?.fields().to_vec();
+
+        let chunk = data.take_shortest_to_chunk()?;
+
+        let mut fw = parquet_write::FileWriter::try_new(
+            &mut writer,
+            schema,
+            options.writer_options(&specs),
+        )?;
+
+        fw.write(encode_row_group(chunk.arrays(), &parquet_fields, &specs)?)?;
+
+        let _size = fw.end(options.key_value_metadata())?;
+
+        Ok(())
+    }
+
+    /// Writes one row group per chunk, chunks longer than `max_rows` are split.
+    pub fn write_parquet_iter<E, I, W>(
+        schema: Schema,
+        data: I,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<parquet_write::WriteOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let options = options.map(Into::into).unwrap_or_default();
+
+        Self::write_parquet_iter_with(schema, data, writer, max_rows, options)
+    }
+
+    /// Writes one row group per chunk with per column options, see `ParquetWriteOptions`.
+    pub fn write_parquet_iter_with<E, I, W>(
+        schema: Schema,
+        data: I,
+        mut writer: W,
+        max_rows: Option<usize>,
+        options: ParquetWriteOptions,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        I: IntoIterator<Item = E>,
+        W: Write,
+    {
+        let specs = options.column_specs(&schema)?;
+        let parquet_fields = parquet_write::to_parquet_schema(&schema)?.fields().to_vec();
+
+        let mut fw = parquet_write::FileWriter::try_new(
+            &mut writer,
+            schema,
+            options.writer_options(&specs),
+        )?;
+
+        for d in data {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                fw.write(encode_row_group(chunk.arrays(), &parquet_fields, &specs)?)?;
+            }
+        }
+
+        let _size = fw.end(options.key_value_metadata())?;
+
+        Ok(())
+    }
+
+    /// Writes one row group per chunk of a receptacle, e.g. `FxBatches`, `FxTable` & `FxTabular`.
+    pub fn write_parquet_from<C: IntoChunks, W: Write>(
+        data: C,
+        writer: W,
+        max_rows: Option<usize>,
         options: Option<parquet_write::WriteOptions>,
     ) -> FxResult<()> {
-        let default_options = parquet_write::WriteOptions {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_parquet_iter(schema, chunks, writer, max_rows, options)
+    }
+
+    /// Writes one row group per chunk of a receptacle with per column options.
+    pub fn write_parquet_from_with<C: IntoChunks, W: Write>(
+        data: C,
+        writer: W,
+        max_rows: Option<usize>,
+        options: ParquetWriteOptions,
+    ) -> FxResult<()> {
+        let (schema, chunks) = data.into_chunks()?;
+
+        Self::write_parquet_iter_with(schema, chunks, writer, max_rows, options)
+    }
+
+    /// Reads all row groups lazily.
+    pub fn read_parquet_iter<D: Eclectic, R: ReadSeek>(
+        reader: R,
+    ) -> FxResult<FxChunkIter<ParquetChunks<R>, D>> {
+        Self::read_parquet_iter_with(reader, ParquetReadOptions::default())
+    }
+
+    /// Reads the projected columns of the row groups which might satisfy the predicate lazily,
+    /// rows are then filtered by the predicate and at most `limit` rows are read.
+    pub fn read_parquet_iter_with<D: Eclectic, R: ReadSeek>(
+        mut reader: R,
+        options: ParquetReadOptions,
+    ) -> FxResult<FxChunkIter<ParquetChunks<R>, D>> {
+        let metadata = parquet_read::read_metadata(&mut reader)?;
+        let schema = parquet_read::infer_schema(&metadata)?;
+
+        let projected = match &options.projection {
+            Some(names) if names.is_empty() => {
+                return Err(FxError::InvalidArgument(
+                    "projection should not be empty".to_string(),
+                ))
+            }
+            Some(names) => names
+                .iter()
+                .map(|n| find_field(&schema, n).cloned())
+                .collect::<FxResult<Vec<_>>>()?,
+            None => schema.fields.clone(),
+        };
+
+        // columns only referenced by the predicate are read for filtering, and dropped afterwards
+        let mut fields = projected.clone();
+        let mut row_groups = metadata.row_groups;
+        if let Some(predicate) = &options.predicate {
+            let predicate = predicate.resolve(&schema)?;
+            let mut stats = Vec::new();
+            for name in predicate.columns() {
+                let field = find_field(&schema, name)?;
+                if !fields.iter().any(|f| f.name == name) {
+                    fields.push(field.clone());
+                }
+                stats.push((name, row_group_stats(field, &row_groups)?));
+            }
+
+            let mut idx = 0;
+            row_groups.retain(|_| {
+                let rg_stats = stats
+                    .iter()
+                    .map(|(name, s)| (*name, s[idx].clone()))
+                    .collect::<HashMap<_, _>>();
+                idx += 1;
+                predicate.might_match(&rg_stats)
+            });
+        }
+
+        let read_schema = Schema::from(fields);
+        let reader = parquet_read::FileReader::new(
+            reader,
+            row_groups,
+            read_schema.clone(),
+            None,
+            None,
+            None,
+        );
+        let chunks = ParquetChunks {
+            reader,
+            schema: read_schema,
+            predicate: options.predicate,
+            projected: projected.len(),
+            remaining: options.limit,
+        };
+
+        let iter = FxChunkIter::new(
+            Schema::from(projected).with_metadata(schema.metadata),
+            chunks,
+        );
+
+        match &options.expected_schema {
+            Some(expected) => iter.expect_schema(expected),
+            None => Ok(iter),
+        }
+    }
+
+    /// Reads all row groups into a receptacle, e.g. `FxBatches`, `Vec<ChunkArr>` & `FxTabular`.
+    pub fn read_parquet_into<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(reader: R) -> FxResult<C> {
+        Self::read_parquet_iter::<D, R>(reader)?.collect_into()
+    }
+
+    /// Reads the filtered row groups into a receptacle, see `read_parquet_iter_with`.
+    pub fn read_parquet_into_with<C: FromChunks<D>, D: Eclectic, R: ReadSeek>(
+        reader: R,
+        options: ParquetReadOptions,
+    ) -> FxResult<C> {
+        Self::read_parquet_iter_with::<D, R>(reader, options)?.collect_into()
+    }
+
+    /// Reads all row groups and concatenates them into one `Eclectic`.
+    pub fn read_parquet<D: Eclectic + Purport, R: ReadSeek>(reader: &mut R) -> FxResult<D> {
+        Self::read_parquet_iter::<D, _>(reader)?.concat()
+    }
+
+    /// Reads the filtered row groups into one `Eclectic`, see `read_parquet_iter_with`.
+    pub fn read_parquet_with<D: Eclectic + Purport, R: ReadSeek>(
+        reader: &mut R,
+        options: ParquetReadOptions,
+    ) -> FxResult<D> {
+        Self::read_parquet_iter_with::<D, _>(reader, options)?.concat()
+    }
+}
+
+fn find_field<'a>(schema: &'a Schema, name: &str) -> FxResult<&'a Field> {
+    schema
+        .fields
+        .iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| FxError::InvalidArgument(format!("column `{name}` not found")))
+}
+
+// statistics of a column in each row group
+fn row_group_stats(
+    field: &Field,
+    row_groups: &[parquet_read::RowGroupMetaData],
+) -> FxResult<Vec<ColumnStats>> {
+    let stats = parquet_read::statistics::deserialize(field, row_groups)?;
+
+    let unknown = || vec![FxValue::Null; row_groups.len()];
+    let min = array_values(stats.min_value.as_ref())?.unwrap_or_else(unknown);
+    let max = array_values(stats.max_value.as_ref())?.unwrap_or_else(unknown);
+    let null_count = stats
+        .null_count
+        .as_any()
+        .downcast_ref::<PrimitiveArray<u64>>();
+
+    let res = row_groups
+        .iter()
+        .enumerate()
+        .map(|(i, rg)| ColumnStats {
+            min: min[i].clone(),
+            max: max[i].clone(),
+            null_count: null_count.and_then(|a| a.get(i)),
+            num_rows: rg.num_rows() as u64,
+        })
+        .collect();
+
+    Ok(res)
+}
+
+// ================================================================================================
+// ParquetReadOptions
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct ParquetReadOptions {
+    /// names of the columns to read, in this order
+    pub projection: Option<Vec<String>>,
+    /// row groups are pruned by their statistics, and the remaining rows are filtered
+    pub predicate: Option<FxPredicate>,
+    /// max number of rows
+    pub limit: Option<usize>,
+    /// the (projected) schema read is validated against it, see `check_schema`
+    pub expected_schema: Option<Schema>,
+}
+
+impl ParquetReadOptions {
+    pub fn with_projection<I, T>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.projection = Some(names.into_iter().map(|n| n.as_ref().to_string()).collect());
+        self
+    }
+
+    pub fn with_predicate(mut self, predicate: FxPredicate) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_expected_schema(mut self, schema: Schema) -> Self {
+        self.expected_schema = Some(schema);
+        self
+    }
+}
+
+// ================================================================================================
+// ParquetChunks
+// ================================================================================================
+
+pub struct ParquetChunks<R: ReadSeek> {
+    reader: parquet_read::FileReader<R>,
+    // projected fields first, then the ones only referenced by the predicate
+    schema: Schema,
+    predicate: Option<FxPredicate>,
+    projected: usize,
+    remaining: Option<usize>,
+}
+
+impl<R: ReadSeek> ParquetChunks<R> {
+    fn filter_rows(&self, chunk: Chunk<Box<dyn Array>>) -> FxResult<Chunk<Box<dyn Array>>> {
+        let chunk = match &self.predicate {
+            Some(p) => filter_chunk(&chunk, &p.evaluate(&self.schema, chunk.arrays())?)?,
+            None => chunk,
+        };
+
+        let mut arrays = chunk.into_arrays();
+        arrays.truncate(self.projected);
+
+        Ok(Chunk::try_new(arrays)?)
+    }
+}
+
+impl<R: ReadSeek> Iterator for ParquetChunks<R> {
+    type Item = FxResult<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining == Some(0) {
+                return None;
+            }
+
+            let chunk = self.reader.next()?.map_err(FxError::from);
+            let mut chunk = match chunk.and_then(|c| self.filter_rows(c)) {
+                Ok(c) => c,
+                Err(e) => return Some(Err(e)),
+            };
+
+            // row groups without matched rows
+            if chunk.is_empty() {
+                continue;
+            }
+
+            if let Some(remaining) = self.remaining.as_mut() {
+                if chunk.len() > *remaining {
+                    let arrays = chunk.arrays().iter().map(|a| a.sliced(0, *remaining));
+                    chunk = Chunk::new(arrays.collect());
+                }
+                *remaining -= chunk.len();
+            }
+
+            return Some(Ok(chunk));
+        }
+    }
+}
+
+// ================================================================================================
+// ParquetWriteOptions
+//
+// File-wide options, overridden per column. The default writes uncompressed plain pages, same as
+// `write_parquet(.., None)` always did; `compact` compresses by Snappy and picks encodings by type:
+// dictionary for strings & binaries, delta for temporal types and plain for the rest. Encodings
+// by type are therefore opt-in, through `compact` or `with_encoding(None)`.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct ParquetWriteOptions {
+    pub write_statistics: bool,
+    /// codec & level, e.g. `CompressionOptions::Zstd(Some(ZstdLevel::try_new(3)?))`
+    pub compression: parquet_write::CompressionOptions,
+    pub version: parquet_write::Version,
+    /// max size of a data page in bytes, defaults to 1MB
+    pub data_pagesize_limit: Option<usize>,
+    /// encoding of all columns, `None` picks one by the column's type
+    pub encoding: Option<parquet_write::Encoding>,
+    pub columns: HashMap<String, ParquetColumnOptions>,
+    /// key-value file metadata
+    pub metadata: Vec<(String, Option<String>)>,
+}
+
+/// Per column overrides, `None` falls back to the file-wide option.
+#[derive(Debug, Clone, Default)]
+pub struct ParquetColumnOptions {
+    /// `None` takes `ParquetWriteOptions::encoding`, which is plain by default rather than the
+    /// encoding picked by the column's type
+    pub encoding: Option<parquet_write::Encoding>,
+    pub compression: Option<parquet_write::CompressionOptions>,
+    /// `Some(false)` also stops writing page indexes for the whole file, since page indexes
+    /// require statistics of every column
+    pub write_statistics: Option<bool>,
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        Self {
             write_statistics: true,
             compression: parquet_write::CompressionOptions::Uncompressed,
             version: parquet_write::Version::V2,
             data_pagesize_limit: None,
-        };
-        let options = options.unwrap_or(default_options);
+            encoding: Some(parquet_write::Encoding::Plain),
+            columns: HashMap::new(),
+            metadata: Vec::new(),
+        }
+    }
+}
 
-        let schema = data.schema().clone();
+impl From<parquet_write::WriteOptions> for ParquetWriteOptions {
+    fn from(options: parquet_write::WriteOptions) -> Self {
+        Self {
+            write_statistics: options.write_statistics,
+            compression: options.compression,
+            version: options.version,
+            data_pagesize_limit: options.data_pagesize_limit,
+            ..Default::default()
+        }
+    }
+}
+
+impl ParquetWriteOptions {
+    /// Snappy compression, and encodings picked by the columns' types.
+    pub fn compact() -> Self {
+        Self {
+            compression: parquet_write::CompressionOptions::Snappy,
+            encoding: None,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_statistics(mut self, write_statistics: bool) -> Self {
+        self.write_statistics = write_statistics;
+        self
+    }
+
+    pub fn with_compression(mut self, compression: parquet_write::CompressionOptions) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_version(mut self, version: parquet_write::Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn with_page_size(mut self, bytes: usize) -> Self {
+        self.data_pagesize_limit = Some(bytes);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Option<parquet_write::Encoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_column_encoding<S: Into<String>>(
+        mut self,
+        column: S,
+        encoding: parquet_write::Encoding,
+    ) -> Self {
+        self.columns.entry(column.into()).or_default().encoding = Some(encoding);
+        self
+    }
+
+    pub fn with_column_compression<S: Into<String>>(
+        mut self,
+        column: S,
+        compression: parquet_write::CompressionOptions,
+    ) -> Self {
+        self.columns.entry(column.into()).or_default().compression = Some(compression);
+        self
+    }
+
+    /// Turning statistics off for a column drops the page indexes of all columns.
+    pub fn with_column_statistics<S: Into<String>>(
+        mut self,
+        column: S,
+        write_statistics: bool,
+    ) -> Self {
+        self.columns
+            .entry(column.into())
+            .or_default()
+            .write_statistics = Some(write_statistics);
+        self
+    }
 
-        let encodings = data
-            .schema()
+    pub fn with_metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.metadata.push((key.into(), Some(value.into())));
+        self
+    }
+
+    fn file_options(&self) -> parquet_write::WriteOptions {
+        parquet_write::WriteOptions {
+            write_statistics: self.write_statistics,
+            compression: self.compression,
+            version: self.version,
+            data_pagesize_limit: self.data_pagesize_limit,
+        }
+    }
+
+    // page indexes are written along with statistics, and they require statistics of all pages
+    fn writer_options(&self, specs: &[ColumnWriteSpec]) -> parquet_write::WriteOptions {
+        parquet_write::WriteOptions {
+            write_statistics: specs.iter().all(|s| s.options.write_statistics),
+            ..self.file_options()
+        }
+    }
+
+    fn key_value_metadata(&self) -> Option<Vec<parquet_write::KeyValue>> {
+        if self.metadata.is_empty() {
+            return None;
+        }
+
+        let kv = self
+            .metadata
+            .iter()
+            .map(|(k, v)| parquet_write::KeyValue::new(k.clone(), v.clone()))
+            .collect();
+
+        Some(kv)
+    }
+
+    // resolved options of each field, fails on unknown columns & unsupported encodings
+    fn column_specs(&self, schema: &Schema) -> FxResult<Vec<ColumnWriteSpec>> {
+        if let Some(name) = self.columns.keys().find(|n| find_field(schema, n).is_err()) {
+            return Err(FxError::InvalidArgument(format!(
+                "column `{name}` not found"
+            )));
+        }
+
+        schema
             .fields
             .iter()
-            .map(|f| parquet_write::transverse(f.data_type(), |_| parquet_write::Encoding::Plain))
-            .collect::<Vec<_>>();
+            .map(|field| {
+                let column = self.columns.get(&field.name);
 
-        let iter = vec![Ok(data.take_shortest_to_chunk()?)];
+                let mut options = self.file_options();
+                if let Some(c) = column.and_then(|c| c.compression) {
+                    options.compression = c;
+                }
+                if let Some(s) = column.and_then(|c| c.write_statistics) {
+                    options.write_statistics = s;
+                }
 
-        let row_groups = parquet_write::RowGroupIterator::try_new(
-            iter.into_iter(),
-            &schema,
-            options,
-            encodings,
-        )?;
+                let encoding = column
+                    .and_then(|c| c.encoding)
+                    .or(self.encoding)
+                    .unwrap_or_else(|| default_encoding(field.data_type()));
 
-        let mut fw = parquet_write::FileWriter::try_new(&mut writer, schema.clone(), options)?;
+                let data_type = field.data_type();
+                let is_dictionary =
+                    matches!(
+                        encoding,
+                        parquet_write::Encoding::RleDictionary
+                            | parquet_write::Encoding::PlainDictionary
+                    ) && !matches!(data_type.to_logical_type(), DataType::Dictionary(..));
+                let dictionary = is_dictionary.then(|| {
+                    DataType::Dictionary(IntegerType::UInt32, Box::new(data_type.clone()), false)
+                });
 
-        for group in row_groups {
-            fw.write(group?)?;
-        }
+                let encoded_type = dictionary.as_ref().unwrap_or(data_type);
+                let leaves = parquet_write::transverse(encoded_type, |dt| dt.clone());
+                if leaves
+                    .iter()
+                    .any(|dt| !parquet_write::can_encode(dt, encoding))
+                {
+                    return Err(FxError::InvalidArgument(format!(
+                        "column `{}` cannot be encoded as {encoding:?}",
+                        field.name
+                    )));
+                }
 
-        let _size = fw.end(None)?;
+                Ok(ColumnWriteSpec {
+                    options,
+                    encodings: vec![encoding; leaves.len()],
+                    dictionary,
+                })
+            })
+            .collect()
+    }
+}
 
-        Ok(())
+fn default_encoding(data_type: &DataType) -> parquet_write::Encoding {
+    match data_type.to_logical_type() {
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::Dictionary(..) => parquet_write::Encoding::RleDictionary,
+        DataType::Date32
+        | DataType::Date64
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => parquet_write::Encoding::DeltaBinaryPacked,
+        _ => parquet_write::Encoding::Plain,
     }
+}
 
-    pub fn read_parquet<D: Eclectic + Purport, R: ReadSeek>(reader: &mut R) -> FxResult<D> {
-        let metadata = parquet_read::read_metadata(reader)?;
+struct ColumnWriteSpec {
+    options: parquet_write::WriteOptions,
+    encodings: Vec<parquet_write::Encoding>,
+    // dictionary encoded columns are cast into this type first
+    dictionary: Option<DataType>,
+}
 
-        let schema = parquet_read::infer_schema(&metadata)?;
+// ================================================================================================
+// Parquet async
+// ================================================================================================
+
+async fn read_row_group_async<R>(
+    reader: &mut R,
+    row_group: &parquet_read::RowGroupMetaData,
+    fields: &[Field],
+) -> arrow2::error::Result<Chunk<Box<dyn Array>>>
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send,
+{
+    let mut arrays = Vec::with_capacity(fields.len());
+    for field in fields {
+        let mut columns = Vec::new();
+        for meta in parquet_read::get_field_columns(row_group.columns(), &field.name) {
+            let (start, length) = meta.byte_range();
+            reader.seek(std::io::SeekFrom::Start(start)).await?;
 
-        for field in &schema.fields {
-            let _statistics = parquet_read::statistics::deserialize(field, &metadata.row_groups)?;
+            let mut chunk = Vec::new();
+            chunk.try_reserve(length as usize)?;
+            reader.take(length).read_to_end(&mut chunk).await?;
+            columns.push((meta, chunk));
         }
 
-        let row_groups = metadata.row_groups;
+        arrays.push(deserialize_field(
+            columns,
+            field.clone(),
+            row_group.num_rows(),
+        )?);
+    }
+
+    Chunk::try_new(arrays)
+}
 
-        let chunks = parquet_read::FileReader::new(reader, row_groups, schema, None, None, None);
+impl FxIO {
+    pub async fn write_parquet_async<D: Eclectic + Purport, W: AsyncWrite + Unpin + Send>(
+        data: D,
+        writer: W,
+        options: Option<parquet_write::WriteOptions>,
+    ) -> FxResult<()> {
+        let schema = data.schema().clone();
 
-        let mut res = None;
-        // only loop once, since parquet's file reader has no size limit
-        for maybe_chunk in chunks {
-            let data = maybe_chunk?
-                .into_arrays()
-                .into_iter()
-                .map(FxSeq::from_box_arr)
-                .collect::<Vec<_>>();
+        Self::write_parquet_stream(schema, futures::stream::iter([data]), writer, None, options)
+            .await
+    }
+
+    /// Writes one row group per chunk of a stream, chunks longer than `max_rows` are split.
+    pub async fn write_parquet_stream<E, S, W>(
+        schema: Schema,
+        data: S,
+        writer: W,
+        max_rows: Option<usize>,
+        options: Option<parquet_write::WriteOptions>,
+    ) -> FxResult<()>
+    where
+        E: Eclectic,
+        S: Stream<Item = E>,
+        W: AsyncWrite + Unpin + Send,
+    {
+        // same defaults as the sync writers, with plain encodings
+        let options: ParquetWriteOptions = options.map(Into::into).unwrap_or_default();
+        let specs = options.column_specs(&schema)?;
+        let encodings = specs.iter().map(|s| s.encodings.clone()).collect();
+
+        let mut sink = parquet_write::FileSink::try_new(
+            writer,
+            schema,
+            encodings,
+            options.writer_options(&specs),
+        )?;
 
-            res = Some(D::from_vec_seq(data));
+        let mut data = std::pin::pin!(data);
+        while let Some(d) = data.next().await {
+            for chunk in eclectic_to_sized_chunks(d, max_rows)? {
+                sink.feed(chunk).await?;
+            }
         }
+        sink.close().await?;
+
+        Ok(())
+    }
+
+    /// Reads all row groups lazily.
+    pub async fn read_parquet_stream<'a, D, R>(
+        mut reader: R,
+    ) -> FxResult<FxChunkStream<BoxStream<'a, arrow2::error::Result<Chunk<Box<dyn Array>>>>, D>>
+    where
+        D: Eclectic,
+        R: AsyncRead + AsyncSeek + Unpin + Send + 'a,
+    {
+        let metadata = parquet_read::read_metadata_async(&mut reader).await?;
+        let schema = parquet_read::infer_schema(&metadata)?;
 
-        res.unwrap()
+        let fields = schema.fields.clone();
+        let row_groups = metadata.row_groups.into_iter();
+        let chunks = futures::stream::try_unfold(
+            (reader, row_groups, fields),
+            |(mut reader, mut row_groups, fields)| async move {
+                let Some(rg) = row_groups.next() else {
+                    return Ok(None);
+                };
+                let chunk = read_row_group_async(&mut reader, &rg, &fields).await?;
+
+                Ok(Some((chunk, (reader, row_groups, fields))))
+            },
+        )
+        .boxed();
+
+        Ok(FxChunkStream::new(schema, chunks))
+    }
+
+    /// Reads all row groups and concatenates them into one `Eclectic`.
+    pub async fn read_parquet_async<D, R>(reader: R) -> FxResult<D>
+    where
+        D: Eclectic,
+        R: AsyncRead + AsyncSeek + Unpin + Send,
+    {
+        Self::read_parquet_stream::<D, R>(reader)
+            .await?
+            .concat()
+            .await
     }
 }
 
@@ -95,28 +771,243 @@ impl FxIO {
 // ================================================================================================
 
 impl<T: Eclectic + Purport> SimpleIO<T> {
+    pub fn write_parquet(&mut self, options: Option<parquet_write::WriteOptions>) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_parquet(data, writer, options))
+    }
+
+    pub fn write_parquet_with(&mut self, options: ParquetWriteOptions) -> FxResult<()> {
+        self.write_data(|data, writer| FxIO::write_parquet_with(data, writer, options))
+    }
+
+    pub fn read_parquet(&mut self) -> FxResult<()> {
+        if self.reader.is_none() {
+            return Err(FxError::EmptyContent);
+        }
+
+        let mut reader = self.reader.take().unwrap();
+
+        self.data = Some(FxIO::read_parquet::<T, _>(&mut reader)?);
+
+        Ok(())
+    }
+
+    pub fn read_parquet_with(&mut self, options: ParquetReadOptions) -> FxResult<()> {
+        if self.reader.is_none() {
+            return Err(FxError::EmptyContent);
+        }
+
+        let mut reader = self.reader.take().unwrap();
+
+        self.data = Some(FxIO::read_parquet_with::<T, _>(&mut reader, options)?);
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// ParallelIO
+// ================================================================================================
+
+impl ParallelIO {
+    /// Each aligned row batch of the `FxTabular` becomes a row group, whose column chunks are
+    /// encoded & compressed on worker threads.
     pub fn write_parquet(&mut self, options: Option<parquet_write::WriteOptions>) -> FxResult<()> {
         if self.data.is_none() || self.writer.is_none() {
             return Err(FxError::EmptyContent);
         }
 
-        let writer = self.writer.take().unwrap();
-        let data = self.take_data().unwrap();
+        let parallel_num = self.parallel_num();
+        let mut writer = self.writer.take().unwrap();
+        let data = self.task_data().unwrap();
+
+        let options: ParquetWriteOptions = options.map(Into::into).unwrap_or_default();
+
+        let (schema, chunks) = data.into_chunks()?;
+        let specs = options.column_specs(&schema)?;
+        let parquet_fields = parquet_write::to_parquet_schema(&schema)?.fields().to_vec();
+
+        let mut fw = parquet_write::FileWriter::try_new(
+            &mut writer,
+            schema,
+            options.writer_options(&specs),
+        )?;
+
+        for chunk in chunks {
+            let columns = chunk
+                .into_arrays()
+                .into_iter()
+                .zip(parquet_fields.iter().cloned())
+                .zip(specs.iter())
+                .collect::<Vec<_>>();
+
+            // one entry per parquet leaf column
+            let mut leaves = Vec::new();
+            par_map_ordered(
+                columns,
+                parallel_num,
+                |((array, type_), spec)| encode_field(array.as_ref(), type_, spec),
+                |pages| {
+                    leaves.extend(pages);
+                    Ok(())
+                },
+            )?;
+
+            fw.write(leaves_to_row_group(leaves))?;
+        }
+
+        let _size = fw.end(options.key_value_metadata())?;
 
-        FxIO::write_parquet(data, writer, options)
+        Ok(())
     }
 
+    /// Column chunks of all the row groups are read sequentially, and decoded on worker threads.
     pub fn read_parquet(&mut self) -> FxResult<()> {
         if self.reader.is_none() {
             return Err(FxError::EmptyContent);
         }
 
+        let parallel_num = self.parallel_num();
         let mut reader = self.reader.take().unwrap();
 
-        self.data = Some(FxIO::read_parquet::<T, _>(&mut reader)?);
+        let metadata = parquet_read::read_metadata(&mut reader)?;
+        let schema = parquet_read::infer_schema(&metadata)?;
+
+        let mut columns = Vec::new();
+        for rg in metadata.row_groups.iter() {
+            for field in schema.fields.iter() {
+                let cols = parquet_read::read_columns(&mut reader, rg.columns(), &field.name)?;
+                columns.push((cols, field.clone(), rg.num_rows()));
+            }
+        }
+
+        let mut arrays = Vec::new();
+        par_map_ordered(
+            columns,
+            parallel_num,
+            |(cols, field, num_rows)| Ok(deserialize_field(cols, field, num_rows)?),
+            |arr| {
+                arrays.push(arr);
+                Ok(())
+            },
+        )?;
+
+        let width = schema.fields.len().max(1);
+        let mut chunks = Vec::new();
+        while !arrays.is_empty() {
+            let rest = arrays.split_off(width.min(arrays.len()));
+            chunks.push(Chunk::try_new(std::mem::replace(&mut arrays, rest)));
+        }
+
+        let data = FxChunkIter::<_, ChunkArr>::new(schema, chunks.into_iter()).collect_into()?;
+        self.data = Some(data);
+
+        Ok(())
+    }
+}
+
+// column chunks of a field into one array
+fn deserialize_field(
+    columns: Vec<(&parquet_read::ColumnChunkMetaData, Vec<u8>)>,
+    field: Field,
+    num_rows: usize,
+) -> arrow2::error::Result<Box<dyn Array>> {
+    let arrs = parquet_read::to_deserializer(columns, field, num_rows, None, None)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match arrs.len() {
+        1 => Ok(arrs.into_iter().next().unwrap()),
+        _ => {
+            let arrs = arrs.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+            concatenate(&arrs)
+        }
+    }
+}
+
+// already compressed pages of a column chunk
+struct CompressedPages {
+    pages: std::vec::IntoIter<parquet_write::CompressedPage>,
+    current: Option<parquet_write::CompressedPage>,
+}
+
+impl CompressedPages {
+    fn new(pages: Vec<parquet_write::CompressedPage>) -> Self {
+        Self {
+            pages: pages.into_iter(),
+            current: None,
+        }
+    }
+}
 
+impl parquet_write::FallibleStreamingIterator for CompressedPages {
+    type Item = parquet_write::CompressedPage;
+    type Error = arrow2::error::Error;
+
+    fn advance(&mut self) -> Result<(), Self::Error> {
+        self.current = self.pages.next();
         Ok(())
     }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.current.as_ref()
+    }
+}
+
+fn encode_column(
+    array: &dyn Array,
+    type_: parquet_write::ParquetType,
+    options: parquet_write::WriteOptions,
+    encoding: &[parquet_write::Encoding],
+) -> FxResult<Vec<Vec<parquet_write::CompressedPage>>> {
+    parquet_write::array_to_columns(array, type_, options, encoding)?
+        .into_iter()
+        .map(|pages| {
+            pages
+                .map(|page| {
+                    let page = parquet_write::compress(page?, vec![], options.compression)
+                        .map_err(arrow2::error::Error::from)?;
+                    Ok(page)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// casts dictionary encoded columns, then encodes & compresses
+fn encode_field(
+    array: &dyn Array,
+    type_: parquet_write::ParquetType,
+    spec: &ColumnWriteSpec,
+) -> FxResult<Vec<Vec<parquet_write::CompressedPage>>> {
+    match &spec.dictionary {
+        Some(dt) => {
+            let array = cast(array, dt, CastOptions::default())?;
+            encode_column(array.as_ref(), type_, spec.options, &spec.encodings)
+        }
+        None => encode_column(array, type_, spec.options, &spec.encodings),
+    }
+}
+
+fn leaves_to_row_group(
+    leaves: Vec<Vec<parquet_write::CompressedPage>>,
+) -> parquet_write::RowGroupIter<'static, arrow2::error::Error> {
+    parquet_write::DynIter::new(leaves.into_iter().map(|pages| {
+        Ok(parquet_write::DynStreamingIterator::new(
+            CompressedPages::new(pages),
+        ))
+    }))
+}
+
+fn encode_row_group<A: AsRef<dyn Array>>(
+    arrays: &[A],
+    parquet_fields: &[parquet_write::ParquetType],
+    specs: &[ColumnWriteSpec],
+) -> FxResult<parquet_write::RowGroupIter<'static, arrow2::error::Error>> {
+    let mut leaves = Vec::new();
+    for ((array, type_), spec) in arrays.iter().zip(parquet_fields).zip(specs) {
+        leaves.extend(encode_field(array.as_ref(), type_.clone(), spec)?);
+    }
+
+    Ok(leaves_to_row_group(leaves))
 }
 
 // ================================================================================================
@@ -192,4 +1083,412 @@ mod test_parquet {
 
         println!("{:?}", ref_data.unwrap());
     }
+
+    #[test]
+    fn parquet_read_multi_row_groups_success() {
+        use std::io::Cursor;
+
+        use crate::cont::{ChunkArr, FxBatches, FxTabular};
+
+        let batch = FxBatch::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        let schema = batch.schema().clone();
+        let options = parquet_write::WriteOptions {
+            write_statistics: true,
+            compression: parquet_write::CompressionOptions::Uncompressed,
+            version: parquet_write::Version::V2,
+            data_pagesize_limit: None,
+        };
+        let encodings = schema
+            .fields
+            .iter()
+            .map(|f| parquet_write::transverse(f.data_type(), |_| parquet_write::Encoding::Plain))
+            .collect::<Vec<_>>();
+        let iter = vec![Ok(batch.data().clone()), Ok(batch.data().clone())];
+        let row_groups =
+            parquet_write::RowGroupIterator::try_new(iter.into_iter(), &schema, options, encodings)
+                .unwrap();
+
+        let mut buf = Cursor::new(Vec::new());
+        let mut fw = parquet_write::FileWriter::try_new(&mut buf, schema, options).unwrap();
+        for group in row_groups {
+            fw.write(group.unwrap()).unwrap();
+        }
+        fw.end(None).unwrap();
+
+        buf.set_position(0);
+        let res = FxIO::read_parquet::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+
+        buf.set_position(0);
+        let res: FxBatches<ChunkArr> = FxIO::read_parquet_into(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data.len(), 2);
+
+        buf.set_position(0);
+        let res: FxTabular = FxIO::read_parquet_into::<_, FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+
+        // empty input
+        let res = FxIO::read_parquet::<FxBatch, _>(&mut Cursor::new(Vec::<u8>::new()));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parquet_round_trip_schema_success() {
+        use std::io::Cursor;
+
+        use arrow2::datatypes::{DataType, Field, Schema};
+
+        let a = arc_arr!([Some(1), None, Some(3)]);
+        let b = arc_arr!(["x", "y", "z"]);
+        let data = FxBatch::new_with_names(vec![a, b], ["id", "sym"]);
+        let schema = data.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet(data, &mut buf, None).unwrap();
+
+        buf.set_position(0);
+        let res = FxIO::read_parquet::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["id", "sym"]);
+        assert!(res.schema().fields[0].is_nullable);
+
+        // validated by the expected schema
+        buf.set_position(0);
+        let res = FxIO::read_parquet_iter::<FxBatch, _>(&mut buf)
+            .unwrap()
+            .expect_schema(&schema)
+            .unwrap()
+            .concat();
+        assert!(res.is_ok());
+
+        let unexpected = Schema::from(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("symbol", DataType::Utf8, false),
+        ]);
+        buf.set_position(0);
+        let res = FxIO::read_parquet_iter::<FxBatch, _>(&mut buf)
+            .unwrap()
+            .expect_schema(&unexpected);
+        assert!(res.is_err());
+
+        // or by the read options
+        buf.set_position(0);
+        let options = ParquetReadOptions::default().with_expected_schema(schema);
+        assert!(FxIO::read_parquet_with::<FxBatch, _>(&mut buf, options).is_ok());
+
+        buf.set_position(0);
+        let options = ParquetReadOptions::default().with_expected_schema(unexpected);
+        assert!(FxIO::read_parquet_with::<FxBatch, _>(&mut buf, options).is_err());
+    }
+
+    #[test]
+    fn parquet_write_row_groups_success() {
+        use std::io::Cursor;
+
+        use crate::ab::Dqs;
+        use crate::cont::{ChunkArr, DequeArcArr, FxTabular};
+
+        // ragged deques: [3, 2] & [5]
+        let mut data = FxTabular::new_with_names(
+            vec![arc_arr!([1, 2, 3]), arc_arr!(["a", "b", "c"])],
+            ["c1", "c2"],
+        );
+        data.push_back(vec![arc_arr!([4, 5]), arc_arr!(["d", "e"])])
+            .unwrap();
+        let c2 = data.remove_column("c2").unwrap();
+        let merged = DequeArcArr::new(vec![arc_arr!(["a", "b", "c", "d", "e"])]);
+        data.add_column(c2.0, merged).unwrap();
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet_from(data, &mut buf, Some(2), None).unwrap();
+
+        buf.set_position(0);
+        let res: Vec<ChunkArr> = FxIO::read_parquet_into(&mut buf).unwrap();
+        // [3, 2] -> [2, 1, 2]
+        assert_eq!(
+            res.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![2, 1, 2]
+        );
+
+        buf.set_position(0);
+        let res = FxIO::read_parquet::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.max_len(), Some(5));
+    }
+
+    #[test]
+    fn parallel_write_read_success() {
+        use crate::ab::Dqs;
+        use crate::cont::FxTabular;
+
+        let mut data = FxTabular::new_with_names(
+            vec![arc_arr!([Some(1), None]), arc_arr!(["a", "b"])],
+            ["c1", "c2"],
+        );
+        data.push_back(vec![arc_arr!([Some(3)]), arc_arr!(["c"])])
+            .unwrap();
+
+        let file = "./cache/test_parallel.parquet";
+        let mut parallel = ParallelIO::new_with_data(data.clone());
+        parallel.set_file_writer(file).unwrap();
+        parallel.write_parquet(None).unwrap();
+
+        let mut parallel = ParallelIO::new();
+        parallel.set_parallel_num(2).unwrap();
+        parallel.set_file_reader(file).unwrap();
+        parallel.read_parquet().unwrap();
+
+        let res = parallel.data().unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.deque_lens(), vec![2, 2]);
+        assert_eq!(res.ref_data(), data.ref_data());
+    }
+
+    #[tokio::test]
+    async fn parquet_async_round_trip_success() {
+        use futures::io::Cursor;
+        use futures::TryStreamExt;
+
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1), None, Some(3)]),
+                arc_arr!(["a", "b", "c"]),
+            ],
+            ["c1", "c2"],
+        );
+        let schema = data.schema().clone();
+
+        let mut buf = Cursor::new(Vec::new());
+        let stream = futures::stream::iter([data.clone(), data]);
+        FxIO::write_parquet_stream(schema, stream, &mut buf, Some(2), None)
+            .await
+            .unwrap();
+
+        // the async output is readable by the sync reader
+        let res =
+            FxIO::read_parquet::<FxBatch, _>(&mut std::io::Cursor::new(buf.get_ref())).unwrap();
+        assert_eq!(res.max_len(), Some(6));
+
+        buf.set_position(0);
+        let batches = FxIO::read_parquet_stream::<FxBatch, _>(&mut buf)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            batches.iter().map(|b| b.max_len()).collect::<Vec<_>>(),
+            vec![Some(2), Some(1), Some(2), Some(1)]
+        );
+
+        buf.set_position(0);
+        let res = FxIO::read_parquet_async::<FxBatch, _>(&mut buf)
+            .await
+            .unwrap();
+        assert_eq!(res.names(), vec!["c1", "c2"]);
+        assert_eq!(res.data()[0].null_count(), 2);
+    }
+
+    #[test]
+    fn parquet_read_pushdown_success() {
+        use std::io::Cursor;
+
+        use crate::ab::FxSeq;
+        use crate::cont::{ChunkArr, FxBatches};
+
+        let c1 = (0..100).collect::<Vec<i64>>();
+        let c2 = (0..100).map(|i| format!("s{i:02}")).collect::<Vec<_>>();
+        let c3 = (0..100)
+            .map(|i| if i < 25 { None } else { Some(i as f64) })
+            .collect::<Vec<_>>();
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!(c1.as_slice()),
+                arc_arr!(c2.as_slice()),
+                arc_arr!(c3.as_slice()),
+            ],
+            ["c1", "c2", "c3"],
+        );
+        let schema = data.schema().clone();
+
+        // 4 row groups of 25 rows
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet_iter(schema, [data], &mut buf, Some(25), None).unwrap();
+
+        // pruning by statistics
+        buf.set_position(0);
+        let metadata = parquet_read::read_metadata(&mut buf).unwrap();
+        let schema = parquet_read::infer_schema(&metadata).unwrap();
+        let stats = row_group_stats(&schema.fields[0], &metadata.row_groups).unwrap();
+        let might_match = |p: &FxPredicate| {
+            stats
+                .iter()
+                .filter(|s| p.might_match(&HashMap::from([("c1", (*s).clone())])))
+                .count()
+        };
+        assert_eq!(might_match(&FxPredicate::gt_eq("c1", 60)), 2);
+        assert_eq!(might_match(&FxPredicate::eq("c1", 30)), 1);
+        assert_eq!(
+            might_match(&FxPredicate::lt("c1", 5).or(FxPredicate::gt("c1", 95))),
+            2
+        );
+        let stats = row_group_stats(&schema.fields[2], &metadata.row_groups).unwrap();
+        assert_eq!(stats[0].null_count, Some(25));
+        assert_eq!(stats[1].min, FxValue::F64(25.0));
+
+        // projection, predicate & limit
+        buf.set_position(0);
+        let options = ParquetReadOptions::default()
+            .with_projection(["c2", "c1"])
+            .with_predicate(FxPredicate::gt_eq("c1", 40).and(FxPredicate::lt("c3", 90.0)))
+            .with_limit(30);
+        let res: FxBatches<ChunkArr> = FxIO::read_parquet_into_with(&mut buf, options).unwrap();
+        assert_eq!(res.names(), vec!["c2", "c1"]);
+        assert_eq!(
+            res.data.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+        let c1 = res.data[1][1].to_values().unwrap();
+        assert_eq!(c1.first(), Some(&FxValue::I64(50)));
+        assert_eq!(c1.last(), Some(&FxValue::I64(69)));
+
+        // all-null row group
+        buf.set_position(0);
+        let options = ParquetReadOptions::default().with_predicate(FxPredicate::is_null("c3"));
+        let res = FxIO::read_parquet_with::<FxBatch, _>(&mut buf, options).unwrap();
+        assert_eq!(res.max_len(), Some(25));
+
+        // unknown column
+        buf.set_position(0);
+        let options = ParquetReadOptions::default().with_projection(["c4"]);
+        assert!(FxIO::read_parquet_with::<FxBatch, _>(&mut buf, options).is_err());
+    }
+
+    #[test]
+    fn parquet_write_options_success() {
+        use std::io::Cursor;
+
+        use arrow2::array::PrimitiveArray;
+        use arrow2::datatypes::TimeUnit;
+
+        let ts = PrimitiveArray::<i64>::from([Some(1_000), None, Some(3_000), Some(4_000)])
+            .to(DataType::Timestamp(TimeUnit::Millisecond, None));
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some("a"), None, Some("a"), Some("b")]),
+                std::sync::Arc::new(ts),
+                arc_arr!([1i32, 2, 3, 4]),
+                arc_arr!([true, false, true, true]),
+            ],
+            ["sym", "ts", "v", "flag"],
+        );
+
+        let options = ParquetWriteOptions::compact()
+            .with_compression(parquet_write::CompressionOptions::Zstd(Some(
+                parquet_write::ZstdLevel::try_new(5).unwrap(),
+            )))
+            .with_page_size(1024)
+            .with_column_encoding("v", parquet_write::Encoding::DeltaBinaryPacked)
+            .with_column_compression("flag", parquet_write::CompressionOptions::Uncompressed)
+            .with_column_statistics("flag", false)
+            .with_metadata("source", "test");
+
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet_with(data.clone(), &mut buf, options).unwrap();
+
+        buf.set_position(0);
+        let metadata = parquet_read::read_metadata(&mut buf).unwrap();
+        let columns = metadata.row_groups[0].columns();
+        let encodings = columns
+            .iter()
+            .map(|c| {
+                c.column_chunk()
+                    .meta_data
+                    .as_ref()
+                    .unwrap()
+                    .encodings
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+        assert!(encodings[0].contains(&parquet_write::Encoding::RleDictionary.into()));
+        assert!(encodings[1].contains(&parquet_write::Encoding::DeltaBinaryPacked.into()));
+        assert!(encodings[2].contains(&parquet_write::Encoding::DeltaBinaryPacked.into()));
+        assert!(encodings[3].contains(&parquet_write::Encoding::Plain.into()));
+        assert_eq!(
+            columns[0].compression(),
+            parquet_write::CompressionOptions::Zstd(None).into()
+        );
+        assert_eq!(
+            columns[3].compression(),
+            parquet_write::CompressionOptions::Uncompressed.into()
+        );
+        assert!(columns[0].statistics().is_some());
+        assert!(columns[3].statistics().is_none());
+        // a column opting out of statistics drops the page indexes of the file
+        assert!(columns
+            .iter()
+            .all(|c| c.column_chunk().column_index_offset.is_none()));
+        let kv = metadata.key_value_metadata().as_ref().unwrap();
+        assert!(kv
+            .iter()
+            .any(|kv| kv.key == "source" && kv.value.as_deref() == Some("test")));
+
+        // types are preserved
+        buf.set_position(0);
+        let res = FxIO::read_parquet::<FxBatch, _>(&mut buf).unwrap();
+        assert_eq!(res.schema(), data.schema());
+        assert_eq!(res.data(), data.data());
+
+        // defaults stay uncompressed & plain
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet(data.clone(), &mut buf, None).unwrap();
+        buf.set_position(0);
+        let metadata = parquet_read::read_metadata(&mut buf).unwrap();
+        let sym = &metadata.row_groups[0].columns()[0];
+        assert_eq!(
+            sym.compression(),
+            parquet_write::CompressionOptions::Uncompressed.into()
+        );
+        assert!(!sym
+            .column_chunk()
+            .meta_data
+            .as_ref()
+            .unwrap()
+            .encodings
+            .contains(&parquet_write::Encoding::RleDictionary.into()));
+        assert!(sym.column_chunk().column_index_offset.is_some());
+
+        // a column override without encoding keeps the file-wide plain encoding
+        let options = ParquetWriteOptions::default()
+            .with_column_compression("sym", parquet_write::CompressionOptions::Snappy);
+        let mut buf = Cursor::new(Vec::new());
+        FxIO::write_parquet_with(data.clone(), &mut buf, options).unwrap();
+        buf.set_position(0);
+        let metadata = parquet_read::read_metadata(&mut buf).unwrap();
+        let sym = &metadata.row_groups[0].columns()[0];
+        assert_eq!(
+            sym.compression(),
+            parquet_write::CompressionOptions::Snappy.into()
+        );
+        assert!(!sym
+            .column_chunk()
+            .meta_data
+            .as_ref()
+            .unwrap()
+            .encodings
+            .contains(&parquet_write::Encoding::RleDictionary.into()));
+
+        // unknown column & unsupported encoding
+        let options = ParquetWriteOptions::default()
+            .with_column_encoding("x", parquet_write::Encoding::Plain);
+        let res = FxIO::write_parquet_with(data.clone(), Cursor::new(Vec::new()), options);
+        assert!(res.is_err());
+        let options = ParquetWriteOptions::default()
+            .with_column_encoding("flag", parquet_write::Encoding::DeltaBinaryPacked);
+        let res = FxIO::write_parquet_with(data, Cursor::new(Vec::new()), options);
+        assert!(res.is_err());
+    }
 }
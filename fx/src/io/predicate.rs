@@ -0,0 +1,476 @@
+//! file: predicate.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 23:05:41 Sunday
+//! brief: Predicates for filtering rows & pruning chunks by statistics
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use arrow2::array::{Array, BooleanArray, Utf8Array};
+use arrow2::compute::cast::{cast, CastOptions};
+use arrow2::datatypes::{DataType, Schema, TimeUnit};
+
+use crate::ab::FxSeq;
+use crate::cont::ArcArr;
+use crate::error::{FxError, FxResult};
+use crate::value::FxValue;
+
+// ================================================================================================
+// FxPredicate
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FxCmpOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl FxCmpOp {
+    fn matches(&self, ord: Ordering) -> bool {
+        match self {
+            FxCmpOp::Eq => ord == Ordering::Equal,
+            FxCmpOp::NotEq => ord != Ordering::Equal,
+            FxCmpOp::Lt => ord == Ordering::Less,
+            FxCmpOp::LtEq => ord != Ordering::Greater,
+            FxCmpOp::Gt => ord == Ordering::Greater,
+            FxCmpOp::GtEq => ord != Ordering::Less,
+        }
+    }
+}
+
+/// `col op literal` predicates combined by `and` & `or`, a null never satisfies a comparison.
+///
+/// Dates & timestamps are compared by their physical values (days, milliseconds or the unit of
+/// the timestamp since epoch), literals of them are either such integers, or strings parsed by
+/// the column's type: `2024-01-31` for dates and RFC 3339 (`2024-01-31T08:00:00+00:00`) for
+/// timestamps.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FxPredicate {
+    Cmp(String, FxCmpOp, FxValue),
+    IsNull(String),
+    IsNotNull(String),
+    And(Box<FxPredicate>, Box<FxPredicate>),
+    Or(Box<FxPredicate>, Box<FxPredicate>),
+}
+
+impl FxPredicate {
+    pub fn cmp<S: Into<String>, V: Into<FxValue>>(column: S, op: FxCmpOp, value: V) -> Self {
+        FxPredicate::Cmp(column.into(), op, value.into())
+    }
+
+    pub fn eq<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::Eq, value)
+    }
+
+    pub fn not_eq<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::NotEq, value)
+    }
+
+    pub fn lt<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::Lt, value)
+    }
+
+    pub fn lt_eq<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::LtEq, value)
+    }
+
+    pub fn gt<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::Gt, value)
+    }
+
+    pub fn gt_eq<S: Into<String>, V: Into<FxValue>>(column: S, value: V) -> Self {
+        Self::cmp(column, FxCmpOp::GtEq, value)
+    }
+
+    pub fn is_null<S: Into<String>>(column: S) -> Self {
+        FxPredicate::IsNull(column.into())
+    }
+
+    pub fn is_not_null<S: Into<String>>(column: S) -> Self {
+        FxPredicate::IsNotNull(column.into())
+    }
+
+    pub fn and(self, other: Self) -> Self {
+        FxPredicate::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        FxPredicate::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Names of the referenced columns, without duplicates.
+    pub fn columns(&self) -> Vec<&str> {
+        let mut res = Vec::new();
+        self.collect_columns(&mut res);
+
+        res
+    }
+
+    fn collect_columns<'a>(&'a self, res: &mut Vec<&'a str>) {
+        match self {
+            FxPredicate::Cmp(c, _, _) | FxPredicate::IsNull(c) | FxPredicate::IsNotNull(c) => {
+                if !res.contains(&c.as_str()) {
+                    res.push(c);
+                }
+            }
+            FxPredicate::And(l, r) | FxPredicate::Or(l, r) => {
+                l.collect_columns(res);
+                r.collect_columns(res);
+            }
+        }
+    }
+
+    /// Parses string literals compared with temporal columns of `schema` into their physical
+    /// values, columns missing from `schema` are left untouched.
+    pub(crate) fn resolve(&self, schema: &Schema) -> FxResult<Self> {
+        let res = match self {
+            FxPredicate::Cmp(c, op, FxValue::Str(v)) => {
+                match schema.fields.iter().find(|f| &f.name == c) {
+                    Some(f) if is_temporal(f.data_type()) => {
+                        FxPredicate::Cmp(c.clone(), *op, parse_temporal(v, f.data_type())?)
+                    }
+                    _ => self.clone(),
+                }
+            }
+            FxPredicate::And(l, r) => l.resolve(schema)?.and(r.resolve(schema)?),
+            FxPredicate::Or(l, r) => l.resolve(schema)?.or(r.resolve(schema)?),
+            _ => self.clone(),
+        };
+
+        Ok(res)
+    }
+
+    /// Whether some rows of a chunk might match, given statistics of its columns. Columns without
+    /// statistics are assumed to match. String literals of temporal columns should be resolved.
+    pub(crate) fn might_match(&self, stats: &HashMap<&str, ColumnStats>) -> bool {
+        match self {
+            FxPredicate::Cmp(c, op, v) => stats.get(c.as_str()).is_none_or(|s| s.might_cmp(*op, v)),
+            FxPredicate::IsNull(c) => stats
+                .get(c.as_str())
+                .and_then(|s| s.null_count)
+                .is_none_or(|n| n > 0),
+            FxPredicate::IsNotNull(c) => stats
+                .get(c.as_str())
+                .and_then(|s| s.null_count.map(|n| n < s.num_rows))
+                .unwrap_or(true),
+            FxPredicate::And(l, r) => l.might_match(stats) && r.might_match(stats),
+            FxPredicate::Or(l, r) => l.might_match(stats) || r.might_match(stats),
+        }
+    }
+
+    /// Evaluates row by row, `arrays` are columns of `schema`.
+    pub(crate) fn evaluate(
+        &self,
+        schema: &Schema,
+        arrays: &[Box<dyn Array>],
+    ) -> FxResult<BooleanArray> {
+        let predicate = self.resolve(schema)?;
+        let mut cache = HashMap::new();
+        let mask = predicate.evaluate_rows(schema, arrays, &mut cache)?;
+
+        Ok(BooleanArray::from_slice(mask))
+    }
+
+    fn evaluate_rows<'a>(
+        &'a self,
+        schema: &Schema,
+        arrays: &[Box<dyn Array>],
+        cache: &mut HashMap<&'a str, Vec<FxValue>>,
+    ) -> FxResult<Vec<bool>> {
+        let res = match self {
+            FxPredicate::Cmp(c, op, v) => column_values(schema, arrays, c, cache)?
+                .iter()
+                .map(|x| cmp_values(x, v).is_some_and(|o| op.matches(o)))
+                .collect(),
+            FxPredicate::IsNull(c) => column_values(schema, arrays, c, cache)?
+                .iter()
+                .map(FxValue::is_null)
+                .collect(),
+            FxPredicate::IsNotNull(c) => column_values(schema, arrays, c, cache)?
+                .iter()
+                .map(|x| !x.is_null())
+                .collect(),
+            FxPredicate::And(l, r) => {
+                let l = l.evaluate_rows(schema, arrays, cache)?;
+                let r = r.evaluate_rows(schema, arrays, cache)?;
+                l.into_iter().zip(r).map(|(l, r)| l && r).collect()
+            }
+            FxPredicate::Or(l, r) => {
+                let l = l.evaluate_rows(schema, arrays, cache)?;
+                let r = r.evaluate_rows(schema, arrays, cache)?;
+                l.into_iter().zip(r).map(|(l, r)| l || r).collect()
+            }
+        };
+
+        Ok(res)
+    }
+}
+
+// ================================================================================================
+// ColumnStats
+// ================================================================================================
+
+/// Statistics of a column in a chunk, unknown min & max are `FxValue::Null`.
+#[derive(Debug, Clone)]
+pub(crate) struct ColumnStats {
+    pub min: FxValue,
+    pub max: FxValue,
+    pub null_count: Option<u64>,
+    pub num_rows: u64,
+}
+
+impl ColumnStats {
+    fn might_cmp(&self, op: FxCmpOp, value: &FxValue) -> bool {
+        // only nulls
+        if self.null_count == Some(self.num_rows) {
+            return false;
+        }
+
+        let (lo, hi) = match (cmp_values(&self.min, value), cmp_values(&self.max, value)) {
+            (Some(lo), Some(hi)) => (lo, hi),
+            _ => return true,
+        };
+
+        match op {
+            FxCmpOp::Eq => lo != Ordering::Greater && hi != Ordering::Less,
+            FxCmpOp::NotEq => !(lo == Ordering::Equal && hi == Ordering::Equal),
+            FxCmpOp::Lt => lo == Ordering::Less,
+            FxCmpOp::LtEq => lo != Ordering::Greater,
+            FxCmpOp::Gt => hi == Ordering::Greater,
+            FxCmpOp::GtEq => hi != Ordering::Less,
+        }
+    }
+}
+
+// ================================================================================================
+// Helpers
+// ================================================================================================
+
+/// Types which can be converted into `FxValue`s, temporal types by their physical values.
+pub(crate) fn is_comparable(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Utf8
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Timestamp(_, _)
+    )
+}
+
+fn is_temporal(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _)
+    )
+}
+
+/// Values of an array, `None` if its type is not comparable.
+pub(crate) fn array_values(array: &dyn Array) -> FxResult<Option<Vec<FxValue>>> {
+    if !is_comparable(array.data_type()) {
+        return Ok(None);
+    }
+
+    let array = match array.data_type() {
+        DataType::Date32 => cast(array, &DataType::Int32, CastOptions::default())?,
+        DataType::Date64 | DataType::Timestamp(_, _) => {
+            cast(array, &DataType::Int64, CastOptions::default())?
+        }
+        _ => array.to_boxed(),
+    };
+
+    ArcArr::from_box_arr(array).to_values().map(Some)
+}
+
+// physical value of a date or timestamp literal
+fn parse_temporal(value: &str, datatype: &DataType) -> FxResult<FxValue> {
+    let array = Utf8Array::<i32>::from_slice([value]);
+    let parsed = match datatype {
+        // only nanoseconds are parsed by arrow2, then casted to the column's unit
+        DataType::Timestamp(_, tz) => {
+            let ns = DataType::Timestamp(TimeUnit::Nanosecond, tz.clone());
+            cast(
+                cast(&array, &ns, CastOptions::default())?.as_ref(),
+                datatype,
+                CastOptions::default(),
+            )?
+        }
+        _ => cast(&array, datatype, CastOptions::default())?,
+    };
+    if parsed.is_null(0) {
+        return Err(FxError::InvalidArgument(format!(
+            "`{value}` is not a valid {datatype:?}"
+        )));
+    }
+
+    let mut values =
+        array_values(parsed.as_ref())?.ok_or(FxError::InvalidType(format!("{datatype:?}")))?;
+
+    Ok(values.remove(0))
+}
+
+fn column_values<'a, 'c>(
+    schema: &Schema,
+    arrays: &[Box<dyn Array>],
+    name: &'a str,
+    cache: &'c mut HashMap<&'a str, Vec<FxValue>>,
+) -> FxResult<&'c Vec<FxValue>> {
+    if !cache.contains_key(name) {
+        let idx = schema
+            .fields
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| FxError::InvalidArgument(format!("column `{name}` not found")))?;
+        let values = array_values(arrays[idx].as_ref())?.ok_or_else(|| {
+            FxError::InvalidType(format!("column `{name}` cannot be compared with a literal"))
+        })?;
+        cache.insert(name, values);
+    }
+
+    Ok(&cache[name])
+}
+
+// integers are compared as integers, otherwise numbers are compared as floats
+fn cmp_values(a: &FxValue, b: &FxValue) -> Option<Ordering> {
+    match (a, b) {
+        (FxValue::Bool(a), FxValue::Bool(b)) => Some(a.cmp(b)),
+        (FxValue::Str(a), FxValue::Str(b)) => Some(a.cmp(b)),
+        _ => match (as_int(a), as_int(b)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => as_float(a)?.partial_cmp(&as_float(b)?),
+        },
+    }
+}
+
+fn as_int(v: &FxValue) -> Option<i128> {
+    match *v {
+        FxValue::I8(v) => Some(v as i128),
+        FxValue::I16(v) => Some(v as i128),
+        FxValue::I32(v) => Some(v as i128),
+        FxValue::I64(v) => Some(v as i128),
+        FxValue::U8(v) => Some(v as i128),
+        FxValue::U16(v) => Some(v as i128),
+        FxValue::U32(v) => Some(v as i128),
+        FxValue::U64(v) => Some(v as i128),
+        _ => None,
+    }
+}
+
+fn as_float(v: &FxValue) -> Option<f64> {
+    match *v {
+        FxValue::F32(v) => Some(v as f64),
+        FxValue::F64(v) => Some(v),
+        _ => as_int(v).map(|v| v as f64),
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_predicate {
+
+    use crate::ab::FromSlice;
+    use crate::arc_arr;
+    use crate::cont::FxBatch;
+
+    use super::*;
+
+    #[test]
+    fn might_match_success() {
+        let stats = HashMap::from([(
+            "a",
+            ColumnStats {
+                min: FxValue::I32(10),
+                max: FxValue::I32(20),
+                null_count: Some(0),
+                num_rows: 5,
+            },
+        )]);
+
+        assert!(FxPredicate::gt("a", 15i64).might_match(&stats));
+        assert!(FxPredicate::eq("a", 20.0).might_match(&stats));
+        assert!(!FxPredicate::lt("a", 10).might_match(&stats));
+        assert!(!FxPredicate::is_null("a").might_match(&stats));
+        assert!(!FxPredicate::gt("a", 20)
+            .or(FxPredicate::lt("a", 10))
+            .might_match(&stats));
+        assert!(!FxPredicate::gt("a", 15)
+            .and(FxPredicate::eq("a", 1))
+            .might_match(&stats));
+        // no statistics
+        assert!(FxPredicate::eq("b", "x").might_match(&stats));
+    }
+
+    #[test]
+    fn evaluate_success() {
+        let data = FxBatch::new_with_names(
+            vec![
+                arc_arr!([Some(1), None, Some(3), Some(4)]),
+                arc_arr!(["x", "y", "x", "z"]),
+            ],
+            ["a", "b"],
+        );
+        let arrays = data.data().iter().map(|a| a.to_boxed()).collect::<Vec<_>>();
+
+        let p = FxPredicate::gt_eq("a", 3).or(FxPredicate::is_null("a"));
+        let res = p.evaluate(data.schema(), &arrays).unwrap();
+        assert_eq!(res, BooleanArray::from_slice([false, true, true, true]));
+
+        let p = FxPredicate::eq("b", "x").and(FxPredicate::not_eq("a", 1));
+        let res = p.evaluate(data.schema(), &arrays).unwrap();
+        assert_eq!(res, BooleanArray::from_slice([false, false, true, false]));
+        assert_eq!(p.columns(), vec!["b", "a"]);
+
+        assert!(FxPredicate::eq("c", 1)
+            .evaluate(data.schema(), &arrays)
+            .is_err());
+    }
+
+    #[test]
+    fn evaluate_temporal_success() {
+        use std::sync::Arc;
+
+        use arrow2::array::PrimitiveArray;
+
+        let date = PrimitiveArray::<i32>::from_slice([19000, 19001, 19002]).to(DataType::Date32);
+        let ts = PrimitiveArray::<i64>::from_slice([0, 1_000, 2_000]).to(DataType::Timestamp(
+            TimeUnit::Millisecond,
+            Some("+00:00".to_string()),
+        ));
+        let data = FxBatch::new_with_names(vec![Arc::new(date), Arc::new(ts)], ["date", "ts"]);
+        let arrays = data.data().iter().map(|a| a.to_boxed()).collect::<Vec<_>>();
+
+        let p = FxPredicate::gt_eq("date", "2022-01-09");
+        let res = p.evaluate(data.schema(), &arrays).unwrap();
+        assert_eq!(res, BooleanArray::from_slice([false, true, true]));
+
+        let p = FxPredicate::lt("date", 19001).or(FxPredicate::eq("ts", 2_000));
+        let res = p.evaluate(data.schema(), &arrays).unwrap();
+        assert_eq!(res, BooleanArray::from_slice([true, false, true]));
+
+        let p = FxPredicate::gt("ts", "1970-01-01T00:00:00.500+00:00");
+        let res = p.evaluate(data.schema(), &arrays).unwrap();
+        assert_eq!(res, BooleanArray::from_slice([false, true, true]));
+
+        // unparsable literal
+        assert!(FxPredicate::eq("date", "x")
+            .evaluate(data.schema(), &arrays)
+            .is_err());
+    }
+}
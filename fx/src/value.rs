@@ -3,9 +3,12 @@
 //! date: 2023/01/14 00:18:43 Saturday
 //! brief: Value
 
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
 use crate::macros::impl_from_x_for_value;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FxValue {
     Bool(bool),
     I8(i8),
@@ -49,3 +52,119 @@ impl From<Option<&str>> for FxValue {
         }
     }
 }
+
+impl FxValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, FxValue::Null)
+    }
+}
+
+// ================================================================================================
+// OrdValue
+//
+// `FxValue` compares floats as IEEE 754 does (`NaN != NaN`, `-0.0 == 0.0`), hence it cannot be a
+// key of `HashMap` & `BTreeMap`. `OrdValue` compares floats by `total_cmp` and hashes them by
+// their bits instead, after folding `-0.0` into `0.0` and every `NaN` into a single one, so that
+// the equal floats are still the same key. Values of different variants are ordered by the
+// variants.
+// ================================================================================================
+
+#[derive(Clone)]
+pub(crate) struct OrdValue(pub(crate) FxValue);
+
+impl std::fmt::Debug for OrdValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! canonical_float {
+    ($v:expr, $t:ty) => {
+        if $v.is_nan() {
+            <$t>::NAN
+        } else if *$v == 0.0 {
+            0.0
+        } else {
+            *$v
+        }
+    };
+}
+
+impl OrdValue {
+    fn variant_idx(&self) -> u8 {
+        match self.0 {
+            FxValue::Bool(_) => 0,
+            FxValue::I8(_) => 1,
+            FxValue::I16(_) => 2,
+            FxValue::I32(_) => 3,
+            FxValue::I64(_) => 4,
+            FxValue::U8(_) => 5,
+            FxValue::U16(_) => 6,
+            FxValue::U32(_) => 7,
+            FxValue::U64(_) => 8,
+            FxValue::F32(_) => 9,
+            FxValue::F64(_) => 10,
+            FxValue::Str(_) => 11,
+            FxValue::Null => 12,
+        }
+    }
+}
+
+impl PartialEq for OrdValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrdValue {}
+
+impl PartialOrd for OrdValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (FxValue::Bool(a), FxValue::Bool(b)) => a.cmp(b),
+            (FxValue::I8(a), FxValue::I8(b)) => a.cmp(b),
+            (FxValue::I16(a), FxValue::I16(b)) => a.cmp(b),
+            (FxValue::I32(a), FxValue::I32(b)) => a.cmp(b),
+            (FxValue::I64(a), FxValue::I64(b)) => a.cmp(b),
+            (FxValue::U8(a), FxValue::U8(b)) => a.cmp(b),
+            (FxValue::U16(a), FxValue::U16(b)) => a.cmp(b),
+            (FxValue::U32(a), FxValue::U32(b)) => a.cmp(b),
+            (FxValue::U64(a), FxValue::U64(b)) => a.cmp(b),
+            (FxValue::F32(a), FxValue::F32(b)) => {
+                canonical_float!(a, f32).total_cmp(&canonical_float!(b, f32))
+            }
+            (FxValue::F64(a), FxValue::F64(b)) => {
+                canonical_float!(a, f64).total_cmp(&canonical_float!(b, f64))
+            }
+            (FxValue::Str(a), FxValue::Str(b)) => a.cmp(b),
+            _ => self.variant_idx().cmp(&other.variant_idx()),
+        }
+    }
+}
+
+impl Hash for OrdValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.variant_idx().hash(state);
+        match &self.0 {
+            FxValue::Bool(v) => v.hash(state),
+            FxValue::I8(v) => v.hash(state),
+            FxValue::I16(v) => v.hash(state),
+            FxValue::I32(v) => v.hash(state),
+            FxValue::I64(v) => v.hash(state),
+            FxValue::U8(v) => v.hash(state),
+            FxValue::U16(v) => v.hash(state),
+            FxValue::U32(v) => v.hash(state),
+            FxValue::U64(v) => v.hash(state),
+            FxValue::F32(v) => canonical_float!(v, f32).to_bits().hash(state),
+            FxValue::F64(v) => canonical_float!(v, f64).to_bits().hash(state),
+            FxValue::Str(v) => v.hash(state),
+            FxValue::Null => {}
+        }
+    }
+}
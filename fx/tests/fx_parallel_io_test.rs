@@ -0,0 +1,159 @@
+//! file: fx_parallel_io_test.rs
+//! author: Jacob Xie
+//! date: 2026/10/18 21:02:17 Sunday
+//! brief:
+
+use std::time::Instant;
+
+use arrow2::array::Array;
+use arrow2::compute::concatenate::concatenate;
+use fx::ab::{Dqs, FromVec, Purport};
+use fx::cont::{ArcArr, FxBatch, FxTabular};
+use fx::io::{ParallelIO, SimpleIO};
+
+const BATCHES: usize = 8;
+const ROWS: usize = 50_000;
+
+fn gen_data() -> (FxTabular, FxBatch) {
+    let gen = |b: usize| {
+        let offset = (b * ROWS) as i64;
+        vec![
+            ArcArr::from_vec((offset..offset + ROWS as i64).collect::<Vec<_>>()),
+            ArcArr::from_vec((0..ROWS).map(|i| i as f64 * 0.5).collect::<Vec<_>>()),
+            ArcArr::from_vec((0..ROWS).map(|i| format!("sym_{i}")).collect::<Vec<_>>()),
+        ]
+    };
+
+    let mut tabular = FxTabular::new_with_names(gen(0), ["id", "price", "sym"]);
+    for b in 1..BATCHES {
+        tabular.push_back(gen(b)).unwrap();
+    }
+
+    let batch = FxBatch::new_with_names(
+        vec![
+            ArcArr::from_vec((0..(BATCHES * ROWS) as i64).collect::<Vec<_>>()),
+            ArcArr::from_vec(
+                (0..BATCHES)
+                    .flat_map(|_| (0..ROWS).map(|i| i as f64 * 0.5))
+                    .collect::<Vec<_>>(),
+            ),
+            ArcArr::from_vec(
+                (0..BATCHES)
+                    .flat_map(|_| (0..ROWS).map(|i| format!("sym_{i}")))
+                    .collect::<Vec<_>>(),
+            ),
+        ],
+        ["id", "price", "sym"],
+    );
+
+    (tabular, batch)
+}
+
+// concatenates each column of a tabular, hence it can be compared with a batch
+fn concat_columns(tabular: &FxTabular) -> Vec<Box<dyn Array>> {
+    tabular
+        .ref_data()
+        .iter()
+        .map(|dq| {
+            let arrays = dq.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+            concatenate(&arrays).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn csv_parallel_write_test() {
+    let (tabular, batch) = gen_data();
+    let (simple_file, parallel_file) = ("./cache/simple_tp.csv", "./cache/parallel_tp.csv");
+
+    let mut simple = SimpleIO::new_with_data(batch);
+    simple.set_file_writer(simple_file).unwrap();
+    simple.write_csv(None).unwrap();
+
+    let mut parallel = ParallelIO::new_with_data(tabular);
+    parallel.set_file_writer(parallel_file).unwrap();
+    parallel.write_csv(None).unwrap();
+
+    assert_eq!(
+        std::fs::read(simple_file).unwrap(),
+        std::fs::read(parallel_file).unwrap()
+    );
+}
+
+#[test]
+fn parquet_parallel_write_read_test() {
+    let (tabular, batch) = gen_data();
+    let (simple_file, parallel_file) = ("./cache/simple_tp.parquet", "./cache/parallel_tp.parquet");
+    let expected = batch.data().clone();
+
+    let mut simple = SimpleIO::new_with_data(batch);
+    simple.set_file_writer(simple_file).unwrap();
+    simple.write_parquet(None).unwrap();
+
+    let mut parallel = ParallelIO::new_with_data(tabular);
+    parallel.set_file_writer(parallel_file).unwrap();
+    parallel.write_parquet(None).unwrap();
+
+    // both files hold the same data, despite of different row groups
+    for file in [simple_file, parallel_file] {
+        let mut simple = SimpleIO::<FxBatch>::new();
+        simple.set_file_reader(file).unwrap();
+        simple.read_parquet().unwrap();
+
+        let batch = simple.take_data().unwrap();
+        assert_eq!(batch.names(), vec!["id", "price", "sym"]);
+        assert_eq!(batch.data(), &expected);
+    }
+
+    // the parallel read matches the sequential one
+    let mut parallel = ParallelIO::new();
+    parallel.set_file_reader(parallel_file).unwrap();
+    parallel.read_parquet().unwrap();
+
+    let tabular = parallel.task_data().unwrap();
+    assert_eq!(tabular.names(), vec!["id", "price", "sym"]);
+    assert_eq!(tabular.deque_lens(), vec![BATCHES; 3]);
+    assert_eq!(tabular.ref_data()[0].len_of_arrays(), vec![ROWS; BATCHES]);
+
+    let columns = concat_columns(&tabular);
+    for (res, exp) in columns.iter().zip(expected.arrays()) {
+        assert_eq!(res.as_ref(), exp.as_ref());
+    }
+}
+
+// cargo test --release --test fx_parallel_io_test -- --ignored --nocapture
+#[test]
+#[ignore]
+fn parallel_write_throughput_test() {
+    let (tabular, batch) = gen_data();
+
+    let mut simple = SimpleIO::new_with_data(batch.clone());
+    simple.set_file_writer("./cache/simple_tp.csv").unwrap();
+    let now = Instant::now();
+    simple.write_csv(None).unwrap();
+    println!("simple csv write: {:?}", now.elapsed());
+
+    let mut parallel = ParallelIO::new_with_data(tabular.clone());
+    parallel.set_file_writer("./cache/parallel_tp.csv").unwrap();
+    let now = Instant::now();
+    parallel.write_csv(None).unwrap();
+    println!(
+        "parallel csv write ({} threads): {:?}",
+        parallel.parallel_num(),
+        now.elapsed()
+    );
+
+    let mut simple = SimpleIO::new_with_data(batch);
+    simple.set_file_writer("./cache/simple_tp.parquet").unwrap();
+    let now = Instant::now();
+    simple.write_parquet(None).unwrap();
+    println!("simple parquet write: {:?}", now.elapsed());
+
+    let mut parallel = ParallelIO::new_with_data(tabular);
+    parallel
+        .set_file_writer("./cache/parallel_tp.parquet")
+        .unwrap();
+    let now = Instant::now();
+    parallel.write_parquet(None).unwrap();
+    println!("parallel parquet write: {:?}", now.elapsed());
+}